@@ -0,0 +1,47 @@
+#![allow(non_snake_case)]
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+
+use crate::constants::use_game_constants;
+
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+/**
+ * Changelog sections, as a version title and its entries
+ */
+fn changelog_sections(changelog: &str) -> Vec<(&str, Vec<&str>)> {
+    let mut sections: Vec<(&str, Vec<&str>)> = Vec::new();
+    for line in changelog.lines() {
+        if let Some(title) = line.strip_prefix("## ") {
+            sections.push((title, Vec::new()));
+        } else if let (Some(entry), Some((_, entries))) =
+            (line.strip_prefix("- "), sections.last_mut())
+        {
+            entries.push(entry);
+        }
+    }
+    sections
+}
+
+#[component]
+pub(crate) fn About() -> Element {
+    let version = env!("CARGO_PKG_VERSION");
+    let preset = use_game_constants().preset;
+    rsx! {
+        div {
+            class: "about",
+            h2 {"reasonably {version}"}
+            p {"Balance preset: {preset}"}
+            p {"An incremental game about writing code: hire devs, fight bugs, ship features and simulate the universe."}
+            for (title, entries) in changelog_sections(CHANGELOG) {
+                h3 {"{title}"}
+                ul {
+                    for entry in entries {
+                        li {"{entry}"}
+                    }
+                }
+            }
+        }
+    }
+}
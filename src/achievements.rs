@@ -0,0 +1,52 @@
+#![allow(non_snake_case)]
+use crate::constants::use_game_constants;
+use crate::format_decimal::format_decimal_loc;
+use crate::quest_data::quest_definitions;
+use crate::research_tree::ResearchStatus;
+use crate::state::use_game_state;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+
+/**
+ * Completed quests, the next one is shown with its cost and the others stay hidden
+ */
+#[component]
+pub(crate) fn Achievements() -> Element {
+    let state = use_game_state();
+    let constants = use_game_constants();
+    let notation = state.read().settings.notation;
+    let achievements = quest_definitions(&constants).into_iter().map(|definition| {
+        let (class, label) = match ResearchStatus::of(&definition, &state.read()) {
+            ResearchStatus::Researched => (
+                "achievement completed",
+                format!("✓ {}", definition.button_name),
+            ),
+            ResearchStatus::Available => (
+                "achievement",
+                format!(
+                    "{} ({})",
+                    definition.button_name,
+                    format_decimal_loc(definition.loc_cost, notation)
+                ),
+            ),
+            ResearchStatus::Locked => ("achievement locked", "???".to_string()),
+        };
+        rsx! {
+            li {
+                key: "{definition.research:?}",
+                class: class,
+                "{label}"
+            }
+        }
+    });
+    rsx! {
+        div {
+            class: "achievements",
+            h2 {"achievements"}
+            ul {
+                {achievements}
+            }
+        }
+    }
+}
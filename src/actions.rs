@@ -0,0 +1,330 @@
+use crate::constants::{GameConstants, Research};
+use crate::git::{Commit, MAX_COMMITS};
+use crate::layoffs::LayoffPlan;
+use crate::producers::Producer;
+use crate::research_data::{apply_research_effect, research_definition};
+use crate::snapshot::{Snapshot, StateField};
+use crate::state::State;
+use break_infinity::{afford_geometric_series, sum_geometric_series, Decimal};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/**
+ * Player actions that can be triggered from a button or a keybinding.
+ * `apply` checks requirements and costs so that no input path bypasses them.
+ */
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum Action {
+    Code,
+    Debug,
+    Hire(Producer),
+    // as many as affordable
+    HireMax(Producer),
+    // wipes all loc and bugs, commits are kept
+    Rmrf,
+    // lays off devs following `State::layoff_policy`
+    SmartStaffing,
+    // buys a research or a quest, once
+    Research(Research),
+    // saves a checkpoint of the codebase
+    GitCommit,
+    // restores the checkpoint at this index of `State::commits`, at a cost
+    GitRevert(usize),
+}
+
+/// false for NaN, which costs and counts become once they overflow
+pub(crate) fn at_least(value: &Decimal, minimum: &Decimal) -> bool {
+    matches!(
+        value.partial_cmp(minimum),
+        Some(Ordering::Greater | Ordering::Equal)
+    )
+}
+
+impl Action {
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Action::Code => "code".to_string(),
+            Action::Debug => "debug".to_string(),
+            Action::Hire(producer) => format!("hire {}", producer.name()),
+            Action::HireMax(producer) => format!("hire max {}", producer.name()),
+            Action::Rmrf => "rm -rf".to_string(),
+            Action::SmartStaffing => "smart staffing".to_string(),
+            Action::Research(research) => format!("research {:?}", research),
+            Action::GitCommit => "git commit".to_string(),
+            Action::GitRevert(index) => format!("git revert #{}", index + 1),
+        }
+    }
+
+    pub(crate) fn is_available(&self, state: &State, constants: &GameConstants) -> bool {
+        match self {
+            Action::Code => true,
+            Action::Debug => state.bugs > Decimal::ZERO,
+            Action::Hire(producer) | Action::HireMax(producer) => {
+                state.researched.contains(&producer.require())
+            }
+            Action::Rmrf => state.researched.contains(&Research::Rmrf),
+            Action::SmartStaffing => {
+                state.researched.contains(&Research::SmartStaffing)
+                    && at_least(&state.loc, &LayoffPlan::new(state, constants).severance)
+            }
+            Action::Research(research) => {
+                !state.researched.contains(research)
+                    && research_definition(research, constants).is_some_and(|definition| {
+                        definition
+                            .require
+                            .is_none_or(|require| state.researched.contains(&require))
+                    })
+            }
+            Action::GitCommit => state.researched.contains(&Research::GitCommit),
+            Action::GitRevert(index) => {
+                state.researched.contains(&Research::GitRevert) && *index < state.commits.len()
+            }
+        }
+    }
+
+    /**
+     * Destructive actions ask for a confirmation and can be undone for a short while
+     */
+    pub(crate) fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            Action::Rmrf | Action::SmartStaffing | Action::GitRevert(_)
+        )
+    }
+
+    /// fields that `apply` may change, captured before a destructive action to undo it
+    pub(crate) fn affected_fields(&self) -> &'static [StateField] {
+        match self {
+            Action::Code => &[StateField::Loc, StateField::Bugs],
+            Action::Debug => &[StateField::Bugs],
+            Action::Hire(producer) | Action::HireMax(producer) => match producer {
+                Producer::Interns => &[StateField::Loc, StateField::ManualInterns],
+                Producer::JuniorDevs => &[StateField::Loc, StateField::ManualJuniorDevs],
+                Producer::SeniorDevs => &[StateField::Loc, StateField::ManualSeniorDevs],
+                Producer::Hrs => &[StateField::Loc, StateField::ManualHrs],
+                Producer::Pms => &[StateField::Loc, StateField::ManualPms],
+            },
+            Action::Rmrf => &[StateField::Loc, StateField::Bugs],
+            Action::SmartStaffing => &StateField::LAYOFFS,
+            Action::Research(_) => &[StateField::Loc],
+            // commits are kept, only the oldest one may be dropped
+            Action::GitCommit => &[],
+            Action::GitRevert(_) => &StateField::CODEBASE,
+        }
+    }
+
+    /**
+     * Returns false when the action is not available or not affordable, leaving `state` untouched
+     */
+    pub(crate) fn apply(&self, state: &mut State, constants: &GameConstants) -> bool {
+        if !self.is_available(state, constants) {
+            return false;
+        }
+        match self {
+            Action::Code => {
+                let loc_added = state.loc_per_clicks;
+                state.loc += loc_added;
+                state.bugs += loc_added * state.manual_bugs_ratio;
+            }
+            Action::Debug => {
+                state.bugs = (state.bugs - state.debug_per_clicks).max(&Decimal::ZERO);
+            }
+            Action::Hire(producer) => {
+                let loc_cost = producer.next_loc_cost(state, constants);
+                if !at_least(&state.loc, &loc_cost) {
+                    return false;
+                }
+                state.spend(loc_cost);
+                *producer.manual_mut(state) += Decimal::ONE;
+            }
+            Action::HireMax(producer) => {
+                let base_cost = producer.loc_base_cost(constants);
+                let growth_rate = producer.loc_growth_rate(constants);
+                let owned = producer.manual(state) + Decimal::ONE;
+                let mut count =
+                    afford_geometric_series(&state.loc, &base_cost, &growth_rate, &owned);
+                let mut loc_cost = sum_geometric_series(&count, &base_cost, &growth_rate, &owned);
+                // rounding may overshoot by one hire
+                if loc_cost > state.loc {
+                    count -= Decimal::ONE;
+                    loc_cost = sum_geometric_series(&count, &base_cost, &growth_rate, &owned);
+                }
+                if !at_least(&count, &Decimal::ONE) {
+                    return false;
+                }
+                state.spend(loc_cost);
+                *producer.manual_mut(state) += count;
+            }
+            Action::Rmrf => {
+                state.loc = Decimal::ZERO;
+                state.bugs = Decimal::ZERO;
+            }
+            // the severance is checked by `is_available`
+            Action::SmartStaffing => LayoffPlan::new(state, constants).apply(state, constants),
+            Action::Research(research) => {
+                let Some(definition) = research_definition(research, constants) else {
+                    return false;
+                };
+                if !at_least(&state.loc, &definition.loc_cost) {
+                    return false;
+                }
+                state.spend(definition.loc_cost);
+                state.researched.insert(*research);
+                apply_research_effect(research, state, constants);
+            }
+            Action::GitCommit => {
+                if state.commits.len() >= MAX_COMMITS {
+                    state.commits.remove(0);
+                }
+                state.commits.push(Commit {
+                    game_time: state.game_time,
+                    snapshot: Snapshot::take(state, &StateField::CODEBASE),
+                    loc_spent: state.loc_spent,
+                });
+            }
+            Action::GitRevert(index) => {
+                let commit = &state.commits[*index];
+                // loc spent since the commit paid for purchases the player keeps
+                let spent_since = state.loc_spent - commit.loc_spent;
+                let loc = commit.snapshot.get(StateField::Loc).unwrap_or_default() - spent_since;
+                state.bugs = commit.snapshot.get(StateField::Bugs).unwrap_or_default();
+                // features shipped since the commit are rolled back, features lost since stay lost
+                if let Some(features) = commit.snapshot.get(StateField::Features) {
+                    state.features = features.min(&state.features);
+                }
+                state.loc =
+                    loc.max(&Decimal::ZERO) * (Decimal::ONE - constants.git_revert_loc_loss_ratio);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hire_requires_research_and_loc() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.loc = Decimal::new(1e9);
+        assert!(!Action::Hire(Producer::Interns).apply(&mut state, &constants));
+        state.researched.insert(Research::Internship);
+        state.loc = Decimal::ZERO;
+        assert!(!Action::Hire(Producer::Interns).apply(&mut state, &constants));
+        state.loc = Producer::Interns.next_loc_cost(&state, &constants);
+        assert!(Action::Hire(Producer::Interns).apply(&mut state, &constants));
+        assert_eq!(state.manual_interns, Decimal::ONE);
+        assert_eq!(state.loc, Decimal::ZERO);
+    }
+
+    #[test]
+    fn hire_max_matches_repeated_hires() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.researched.insert(Research::Internship);
+        state.loc = Decimal::new(1e6);
+        let mut one_by_one = State::new(constants.clone());
+        one_by_one.researched.insert(Research::Internship);
+        one_by_one.loc = state.loc;
+        while Action::Hire(Producer::Interns).apply(&mut one_by_one, &constants) {}
+
+        assert!(Action::HireMax(Producer::Interns).apply(&mut state, &constants));
+        // repeated increments accumulate rounding errors
+        assert_eq!(
+            state.manual_interns.to_number(),
+            one_by_one.manual_interns.to_number().round()
+        );
+        assert!(state.loc >= Decimal::ZERO);
+        assert!(state.loc < Producer::Interns.next_loc_cost(&state, &constants));
+    }
+
+    #[test]
+    fn debug_never_makes_bugs_negative() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.bugs = state.debug_per_clicks / Decimal::new(2.0);
+        assert!(Action::Debug.apply(&mut state, &constants));
+        assert_eq!(state.bugs, Decimal::ZERO);
+        assert!(!Action::Debug.apply(&mut state, &constants));
+    }
+
+    #[test]
+    fn research_requires_prerequisite_and_is_bought_once() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.loc = Decimal::new(10.0);
+        assert!(!Action::Research(Research::FizzBuzz).apply(&mut state, &constants));
+        assert!(Action::Research(Research::HelloWorld).apply(&mut state, &constants));
+        assert!(!Action::Research(Research::HelloWorld).apply(&mut state, &constants));
+        assert_eq!(state.loc, Decimal::new(9.0));
+        assert!(!Action::Research(Research::FizzBuzz).apply(&mut state, &constants));
+        assert!(state.researched.contains(&Research::HelloWorld));
+        assert!(!state.researched.contains(&Research::FizzBuzz));
+    }
+
+    #[test]
+    fn rm_rf_keeps_commits_and_revert_restores_them_at_a_cost() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.loc = Decimal::new(1000.0);
+        state.bugs = Decimal::new(10.0);
+        assert!(!Action::GitCommit.apply(&mut state, &constants));
+        state.researched.insert(Research::GitCommit);
+        assert!(Action::GitCommit.apply(&mut state, &constants));
+
+        state.bugs = Decimal::new(1e6);
+        assert!(!Action::GitRevert(0).apply(&mut state, &constants));
+        state.researched.insert(Research::Rmrf);
+        state.researched.insert(Research::GitRevert);
+        assert!(Action::Rmrf.apply(&mut state, &constants));
+        assert!(!Action::GitRevert(1).apply(&mut state, &constants));
+        assert!(Action::GitRevert(0).apply(&mut state, &constants));
+        assert_eq!(
+            state.loc,
+            Decimal::new(1000.0) * (Decimal::ONE - constants.git_revert_loc_loss_ratio)
+        );
+        assert_eq!(state.bugs, Decimal::new(10.0));
+        assert_eq!(state.commits.len(), 1);
+
+        for _ in 0..MAX_COMMITS {
+            Action::GitCommit.apply(&mut state, &constants);
+        }
+        assert_eq!(state.commits.len(), MAX_COMMITS);
+        assert_eq!(
+            state.commits[0].snapshot.get(StateField::Bugs),
+            Some(state.bugs)
+        );
+    }
+
+    #[test]
+    fn revert_never_refunds_kept_purchases() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.researched.extend([
+            Research::Internship,
+            Research::GitCommit,
+            Research::GitRevert,
+        ]);
+        state.loc = Decimal::new(1e6);
+        state.features = Decimal::new(10.0);
+        assert!(Action::GitCommit.apply(&mut state, &constants));
+        let before_hire = state.loc;
+        assert!(Action::HireMax(Producer::Interns).apply(&mut state, &constants));
+        let after_hire = state.loc;
+        let hired = state.manual_interns;
+        state.features = Decimal::new(5.0);
+
+        assert!(Action::GitRevert(0).apply(&mut state, &constants));
+        assert!(state.loc < before_hire);
+        assert!(state.loc <= after_hire);
+        assert_eq!(state.manual_interns, hired);
+        // features lost after the commit stay lost
+        assert_eq!(state.features, Decimal::new(5.0));
+        // features shipped after the commit are rolled back
+        state.features = Decimal::new(20.0);
+        assert!(Action::GitRevert(0).apply(&mut state, &constants));
+        assert_eq!(state.features, Decimal::new(10.0));
+    }
+}
@@ -0,0 +1,477 @@
+//! Balance reports computed from the real `GameConstants`: cost curves, payback times,
+//! the optimal purchase order and what each research is worth, as CSV tables and SVG charts.
+
+use crate::constants::{GameConstants, Research};
+use crate::eta::format_seconds;
+use crate::producers::Producer;
+use crate::quest_data::quest_definitions;
+use crate::rates::Rates;
+use crate::research_data::research_definitions;
+use crate::simulation::{
+    payback_seconds, ReferenceStrategy, Simulation, Strategy, REFERENCE_CLICKS_PER_SECOND,
+    REFERENCE_SEED, TICKS_PER_SECOND,
+};
+use crate::state::State;
+use break_infinity::{sum_geometric_series, Decimal};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const MAX_HEADCOUNT: usize = 600;
+const HEADCOUNT_STEP: usize = 10;
+// producers bought one after the other in the optimal purchase table
+const PURCHASES: usize = 300;
+// game time given to the reference run to reach as many quests as it can
+const REFERENCE_MAX_SECONDS: f64 = 4.0 * 3600.0;
+
+const CHART_WIDTH: f64 = 800.0;
+const CHART_HEIGHT: f64 = 500.0;
+const CHART_MARGIN: f64 = 70.0;
+
+// producers that write code, or hire those who do
+const PRODUCTIVE: [Producer; 4] = [
+    Producer::Interns,
+    Producer::JuniorDevs,
+    Producer::SeniorDevs,
+    Producer::Hrs,
+];
+
+fn color(producer: Producer) -> &'static str {
+    match producer {
+        Producer::Interns => "#0343df",
+        Producer::JuniorDevs => "#f97306",
+        Producer::SeniorDevs => "#15b01a",
+        Producer::Hrs => "#7e1e9c",
+        Producer::Pms => "#e50000",
+    }
+}
+
+/// two decimals while it fits a float, which is plenty for balance and keeps rounding noise out
+fn format_number(value: Decimal) -> String {
+    let number = value.to_number();
+    if number.is_finite() {
+        format!("{:.2}", number)
+    } else {
+        value.to_string()
+    }
+}
+
+pub(crate) struct Table {
+    pub header: &'static [&'static str],
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub(crate) fn csv(&self) -> String {
+        let mut csv = self.header.join(",");
+        csv.push('\n');
+        for row in &self.rows {
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+pub(crate) struct Series {
+    pub name: String,
+    pub color: &'static str,
+    pub points: Vec<(f64, f64)>,
+}
+
+/**
+ * Line chart with a logarithmic y axis, costs and paybacks span many orders of magnitude.
+ * Points that cannot be drawn on it (zero, negative, infinite) are left out.
+ */
+pub(crate) fn line_chart(title: &str, x_label: &str, y_label: &str, series: &[Series]) -> String {
+    let drawable = |(x, y): &(f64, f64)| x.is_finite() && y.is_finite() && *y > 0.0;
+    let points: Vec<(f64, f64)> = series
+        .iter()
+        .flat_map(|series| series.points.iter().copied().filter(drawable))
+        .collect();
+    let x_min = points.iter().map(|(x, _)| *x).fold(f64::INFINITY, f64::min);
+    let x_max = points
+        .iter()
+        .map(|(x, _)| *x)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let y_min = points
+        .iter()
+        .map(|(_, y)| y.log10().floor())
+        .fold(f64::INFINITY, f64::min);
+    let y_max = points
+        .iter()
+        .map(|(_, y)| y.log10().ceil())
+        .fold(f64::NEG_INFINITY, f64::max)
+        .max(y_min + 1.0);
+    let plot_width = CHART_WIDTH - 2.0 * CHART_MARGIN;
+    let plot_height = CHART_HEIGHT - 2.0 * CHART_MARGIN;
+    let to_x = |x: f64| CHART_MARGIN + (x - x_min) / (x_max - x_min).max(f64::EPSILON) * plot_width;
+    let to_y =
+        |y: f64| CHART_HEIGHT - CHART_MARGIN - (y.log10() - y_min) / (y_max - y_min) * plot_height;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{CHART_WIDTH}" height="{CHART_HEIGHT}" viewBox="0 0 {CHART_WIDTH} {CHART_HEIGHT}" font-family="sans-serif" font-size="12">"#
+    )
+    .unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle" font-size="16">{}</text>"#,
+        CHART_WIDTH / 2.0,
+        CHART_MARGIN / 2.0,
+        title
+    )
+    .unwrap();
+    // one grid line per decade
+    for decade in (y_min as i32)..=(y_max as i32) {
+        let y = to_y(10f64.powi(decade));
+        writeln!(
+            svg,
+            r##"<line x1="{CHART_MARGIN}" y1="{y:.1}" x2="{}" y2="{y:.1}" stroke="#ddd"/>"##,
+            CHART_WIDTH - CHART_MARGIN
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{y:.1}" text-anchor="end" dominant-baseline="middle">1e{decade}</text>"#,
+            CHART_MARGIN - 6.0
+        )
+        .unwrap();
+    }
+    for tick in 0..=5 {
+        let x = x_min + (x_max - x_min) * tick as f64 / 5.0;
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{}" text-anchor="middle">{:.0}</text>"#,
+            to_x(x),
+            CHART_HEIGHT - CHART_MARGIN + 18.0,
+            x
+        )
+        .unwrap();
+    }
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+        CHART_WIDTH / 2.0,
+        CHART_HEIGHT - CHART_MARGIN / 3.0,
+        x_label
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r#"<text transform="translate({}, {}) rotate(-90)" text-anchor="middle">{}</text>"#,
+        CHART_MARGIN / 4.0,
+        CHART_HEIGHT / 2.0,
+        y_label
+    )
+    .unwrap();
+    for (index, series) in series.iter().enumerate() {
+        let points: Vec<String> = series
+            .points
+            .iter()
+            .filter(|point| drawable(point))
+            .map(|(x, y)| format!("{:.1},{:.1}", to_x(*x), to_y(*y)))
+            .collect();
+        writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/>"#,
+            points.join(" "),
+            series.color
+        )
+        .unwrap();
+        let legend_y = CHART_MARGIN + 16.0 * index as f64;
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{legend_y}" fill="{}">{}</text>"#,
+            CHART_MARGIN + 10.0,
+            series.color,
+            series.name
+        )
+        .unwrap();
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn headcounts() -> impl Iterator<Item = usize> {
+    (0..=MAX_HEADCOUNT).step_by(HEADCOUNT_STEP)
+}
+
+/**
+ * Cost of the next hire and of all hires so far, over the manual headcount
+ */
+pub(crate) fn cost_curves(constants: &GameConstants) -> (Table, Vec<Series>) {
+    let mut rows = Vec::new();
+    let mut series = Vec::new();
+    for producer in Producer::ALL {
+        let base_cost = producer.loc_base_cost(constants);
+        let growth_rate = producer.loc_growth_rate(constants);
+        let mut points = Vec::new();
+        for headcount in headcounts() {
+            let count = Decimal::new(headcount as f64);
+            let next_cost = base_cost * growth_rate.pow(&(count + Decimal::ONE));
+            let total_cost = sum_geometric_series(&count, &base_cost, &growth_rate, &Decimal::ONE);
+            points.push((headcount as f64, next_cost.to_number()));
+            rows.push(vec![
+                producer.name().to_string(),
+                headcount.to_string(),
+                format_number(next_cost),
+                format_number(total_cost),
+            ]);
+        }
+        series.push(Series {
+            name: format!("{} next hire", producer.name()),
+            color: color(producer),
+            points,
+        });
+    }
+    let table = Table {
+        header: &["producer", "headcount", "next_loc_cost", "total_loc_cost"],
+        rows,
+    };
+    (table, series)
+}
+
+/**
+ * Game time for the next hire to pay for itself, over the manual headcount of its tier
+ */
+pub(crate) fn payback_times(constants: &GameConstants) -> (Table, Vec<Series>) {
+    let mut rows = Vec::new();
+    let mut series = Vec::new();
+    for producer in PRODUCTIVE {
+        let mut state = State::new(constants.clone());
+        let mut points = Vec::new();
+        for headcount in headcounts() {
+            *producer.manual_mut(&mut state) = Decimal::new(headcount as f64);
+            let seconds = payback_seconds(producer, &state, constants);
+            if let Some(seconds) = seconds {
+                points.push((headcount as f64, seconds));
+            }
+            rows.push(vec![
+                producer.name().to_string(),
+                headcount.to_string(),
+                format_seconds(seconds),
+            ]);
+        }
+        series.push(Series {
+            name: producer.name().to_string(),
+            color: color(producer),
+            points,
+        });
+    }
+    let table = Table {
+        header: &["producer", "headcount", "payback_seconds"],
+        rows,
+    };
+    (table, series)
+}
+
+/**
+ * Hires in the order that always picks the fastest payback, from an empty company
+ */
+pub(crate) fn next_purchases(constants: &GameConstants, purchases: usize) -> Table {
+    let mut state = State::new(constants.clone());
+    state
+        .researched
+        .extend(PRODUCTIVE.iter().map(|producer| producer.require()));
+    let mut rows = Vec::new();
+    for purchase in 1..=purchases {
+        let Some((producer, seconds)) = PRODUCTIVE
+            .into_iter()
+            .filter_map(|producer| {
+                payback_seconds(producer, &state, constants).map(|seconds| (producer, seconds))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            break;
+        };
+        let loc_cost = producer.next_loc_cost(&state, constants);
+        *producer.manual_mut(&mut state) += Decimal::ONE;
+        let loc_per_second = Rates::new(&state).loc * Decimal::new(TICKS_PER_SECOND);
+        rows.push(vec![
+            purchase.to_string(),
+            producer.name().to_string(),
+            format!("{:.0}", producer.manual(&state).to_number()),
+            format_number(loc_cost),
+            format_seconds(Some(seconds)),
+            format_number(loc_per_second),
+        ]);
+    }
+    Table {
+        header: &[
+            "purchase",
+            "producer",
+            "headcount",
+            "loc_cost",
+            "payback_seconds",
+            "loc_per_second",
+        ],
+        rows,
+    }
+}
+
+/// game time of the reference strategy to complete `milestone`, `None` if not within `max_seconds`
+fn seconds_to(
+    constants: &GameConstants,
+    strategy: &mut impl Strategy,
+    milestone: Research,
+    max_seconds: f64,
+) -> Option<f64> {
+    let mut simulation = Simulation::new(constants.clone(), REFERENCE_SEED);
+    simulation.run(strategy, milestone, max_seconds);
+    simulation.completed(milestone)
+}
+
+/**
+ * Game time each research saves the reference strategy on its way to the furthest quest it reaches.
+ * Without some researches it takes more than twice as long, or never gets there: they show no time.
+ */
+pub(crate) fn research_values(constants: &GameConstants) -> Table {
+    let mut simulation = Simulation::new(constants.clone(), REFERENCE_SEED);
+    simulation.run(
+        &mut ReferenceStrategy::new(REFERENCE_CLICKS_PER_SECOND),
+        Research::Differentiation,
+        REFERENCE_MAX_SECONDS,
+    );
+    let mut rows = Vec::new();
+    if let Some(&(milestone, with)) = simulation.quests.last() {
+        for definition in research_definitions(constants) {
+            let mut strategy =
+                ReferenceStrategy::new(REFERENCE_CLICKS_PER_SECOND).skipping(definition.research);
+            // twice as long is enough to tell that a research is needed
+            let without = seconds_to(constants, &mut strategy, milestone, 2.0 * with);
+            rows.push(vec![
+                format!("{:?}", definition.research),
+                format_number(definition.loc_cost),
+                format!("{:?}", milestone),
+                format_seconds(Some(with)),
+                format_seconds(without),
+                format_seconds(without.map(|without| without - with)),
+            ]);
+        }
+    }
+    Table {
+        header: &[
+            "research",
+            "loc_cost",
+            "milestone",
+            "seconds_with",
+            "seconds_without",
+            "seconds_saved",
+        ],
+        rows,
+    }
+}
+
+/**
+ * Game time of the reference strategy to each quest, `-` for the ones out of its reach
+ */
+pub(crate) fn quest_timings(constants: &GameConstants) -> Table {
+    let mut simulation = Simulation::new(constants.clone(), REFERENCE_SEED);
+    simulation.run(
+        &mut ReferenceStrategy::new(REFERENCE_CLICKS_PER_SECOND),
+        Research::Differentiation,
+        REFERENCE_MAX_SECONDS,
+    );
+    let rows = quest_definitions(constants)
+        .iter()
+        .map(|definition| {
+            let seconds = simulation.completed(definition.research);
+            vec![
+                format!("{:?}", definition.research),
+                format_number(definition.loc_cost),
+                format_seconds(seconds),
+            ]
+        })
+        .collect();
+    Table {
+        header: &["quest", "loc_cost", "seconds"],
+        rows,
+    }
+}
+
+/**
+ * Writes every report in `directory`, returns the files written
+ */
+pub(crate) fn write_reports(
+    constants: &GameConstants,
+    directory: &Path,
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(directory)?;
+    let (costs, cost_series) = cost_curves(constants);
+    let (paybacks, payback_series) = payback_times(constants);
+    let files = [
+        ("cost_curves.csv", costs.csv()),
+        (
+            "cost_curves.svg",
+            line_chart("hiring costs", "manual headcount", "loc", &cost_series),
+        ),
+        ("payback.csv", paybacks.csv()),
+        (
+            "payback.svg",
+            line_chart(
+                "payback time of the next hire",
+                "manual headcount",
+                "seconds",
+                &payback_series,
+            ),
+        ),
+        (
+            "next_purchases.csv",
+            next_purchases(constants, PURCHASES).csv(),
+        ),
+        ("research_values.csv", research_values(constants).csv()),
+        ("quest_timings.csv", quest_timings(constants).csv()),
+    ];
+    let mut written = Vec::new();
+    for (name, content) in files {
+        let path = directory.join(name);
+        fs::write(&path, content)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_curves_match_hiring_costs() {
+        let constants = GameConstants::default();
+        let (table, _) = cost_curves(&constants);
+        let mut state = State::new(constants.clone());
+        for producer in Producer::ALL {
+            for headcount in [0, 10, 250] {
+                *producer.manual_mut(&mut state) = Decimal::new(headcount as f64);
+                let row = table
+                    .rows
+                    .iter()
+                    .find(|row| row[0] == producer.name() && row[1] == headcount.to_string())
+                    .unwrap();
+                assert_eq!(
+                    row[2],
+                    format_number(producer.next_loc_cost(&state, &constants))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn next_purchases_pick_the_fastest_payback() {
+        let constants = GameConstants::default();
+        let table = next_purchases(&constants, 50);
+        assert_eq!(table.rows.len(), 50);
+        let paybacks: Vec<f64> = table
+            .rows
+            .iter()
+            .map(|row| row[4].parse().unwrap())
+            .collect();
+        // each hire makes the next one of its tier pricier, never cheaper
+        assert!(paybacks.windows(2).all(|pair| pair[0] <= pair[1] + 0.1));
+        assert!(table.csv().starts_with("purchase,producer,"));
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(non_snake_case)]
-use crate::state::State;
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
@@ -7,17 +8,18 @@ use dioxus::prelude::{Signal, Writable};
 
 #[component]
 pub(crate) fn CheatAction(
-    mut state: Signal<State>,
     button_name: String,
     debug_message: String,
     action: EventHandler<Signal<State>>,
 ) -> Element {
+    let mut state = use_game_state();
     rsx! {
         button {
             class: "repeatable-action-button",
             onclick: move |_| {
                 action.call(state);
                 state.write().logs.log(
+                    LogCategory::Cheat,
                     debug_message.as_str()
                 )
         }
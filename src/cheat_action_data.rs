@@ -7,14 +7,14 @@ use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
 use dioxus::prelude::{Signal, Writable};
 
-use crate::state::State;
+use crate::state::{use_game_state, State};
 
 #[component]
-pub(crate) fn CheatActions(mut state: Signal<State>) -> Element {
+pub(crate) fn CheatActions() -> Element {
+    let state = use_game_state();
     rsx! {
         if state.read().researched.contains(&Research::Cheating) {
             CheatAction{
-                state: state,
                 button_name: "cheat loc",
                 debug_message: "cheating loc...",
                 action: move |mut s: Signal<State>| {
@@ -22,7 +22,6 @@ pub(crate) fn CheatActions(mut state: Signal<State>) -> Element {
                 },
             }
             CheatAction{
-                state: state,
                 button_name: "cheat debug",
                 debug_message: "cheating debug...",
                 action: move |mut s: Signal<State>| {
@@ -30,7 +29,6 @@ pub(crate) fn CheatActions(mut state: Signal<State>) -> Element {
                 },
             }
             CheatAction{
-                state: state,
                 button_name: "cheat interns",
                 debug_message: "cheating interns...",
                 action: move |mut s: Signal<State>| {
@@ -38,7 +36,6 @@ pub(crate) fn CheatActions(mut state: Signal<State>) -> Element {
                 },
             }
             CheatAction{
-                state: state,
                 button_name: "cheat junior devs",
                 debug_message: "cheating junior devs...",
                 action: move |mut s: Signal<State>| {
@@ -46,7 +43,6 @@ pub(crate) fn CheatActions(mut state: Signal<State>) -> Element {
                 },
             }
             CheatAction{
-                state: state,
                 button_name: "cheat senior devs",
                 debug_message: "cheating senior devs...",
                 action: move |mut s: Signal<State>| {
@@ -54,7 +50,6 @@ pub(crate) fn CheatActions(mut state: Signal<State>) -> Element {
                 },
             }
             CheatAction{
-                state: state,
                 button_name: "cheat dt faster",
                 debug_message: "cheating dt faster",
                 action: move |mut s: Signal<State>| {
@@ -62,7 +57,6 @@ pub(crate) fn CheatActions(mut state: Signal<State>) -> Element {
                 },
             }
             CheatAction{
-                state: state,
                 button_name: "cheat dt slower",
                 debug_message: "cheating dt slower",
                 action: move |mut s: Signal<State>| {
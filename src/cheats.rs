@@ -0,0 +1,62 @@
+#![allow(non_snake_case)]
+//! Cheat tooling is only compiled in with the `cheats` feature, other builds get empty stand-ins.
+
+use crate::constants::Research;
+use crate::state::State;
+#[cfg(not(feature = "cheats"))]
+use dioxus::prelude::*;
+
+#[cfg(feature = "cheats")]
+pub(crate) use crate::cheat_action_data::CheatActions;
+#[cfg(feature = "cheats")]
+pub(crate) use crate::console::DevConsole;
+
+pub(crate) const CHEATS: bool = cfg!(feature = "cheats");
+
+#[cfg(not(feature = "cheats"))]
+#[component]
+pub(crate) fn CheatActions() -> Element {
+    None
+}
+
+#[cfg(not(feature = "cheats"))]
+#[component]
+pub(crate) fn DevConsole() -> Element {
+    None
+}
+
+/**
+ * Grants cheats to a loaded save in builds with cheats, revokes them in others.
+ * Every build had cheats before the feature existed, older saves had them available.
+ */
+pub(crate) fn update_cheats_availability(state: &mut State) {
+    state.cheats_available |= CHEATS || state.researched.contains(&Research::Cheating);
+    if CHEATS {
+        state.researched.insert(Research::Cheating);
+    } else {
+        state.researched.remove(&Research::Cheating);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::GameConstants;
+
+    #[test]
+    fn saves_remember_cheats_were_available() {
+        let mut state = State::new(GameConstants::default());
+        assert_eq!(state.cheats_available, CHEATS);
+
+        // a save from before the feature
+        state.cheats_available = false;
+        state.researched.insert(Research::Cheating);
+        update_cheats_availability(&mut state);
+        assert!(state.cheats_available);
+        assert_eq!(state.researched.contains(&Research::Cheating), CHEATS);
+
+        // still remembered once loaded by a build without cheats
+        update_cheats_availability(&mut state);
+        assert!(state.cheats_available);
+    }
+}
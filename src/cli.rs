@@ -0,0 +1,207 @@
+//! Command line of native builds, which run the game headlessly for balance work.
+//! The web build only ever launches the game.
+
+use crate::actions::Action;
+use crate::balance::write_reports;
+use crate::constants::{GameConstants, Research};
+use crate::eta::format_seconds;
+use crate::presets::{parse_constants, Preset};
+use crate::quest_data::quest_definitions;
+use crate::simulation::{Replay, Simulation, TICKS_PER_SECOND};
+use crate::solver::{degenerate_findings, format_plans, solve};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+const USAGE: &str = "usage:
+  reasonably [--constants <preset|file.toml>] <command>
+  reasonably balance [directory]          write balance reports
+  reasonably solve [route.json] [quest]   search for a fast route to a quest, the last one by default
+  reasonably replay <route.json>          play a route again, with the seed and constants it was found with
+
+constants come from the normal preset unless given a preset name (normal, fast-test, hardcore)
+or a TOML file, which may inherit a preset and override some of its constants";
+const DEFAULT_REPORTS_DIRECTORY: &str = "balance";
+const DEFAULT_ROUTE_FILE: &str = "route.json";
+// game time the solver gets to go as far as it can
+const SOLVER_MAX_SECONDS: f64 = 4.0 * 3600.0;
+const SOLVER_BEAM_WIDTH: usize = 8;
+
+/**
+ * Route written by `solve`, with what it takes to play it again the same way
+ */
+#[derive(Serialize, Deserialize)]
+struct RouteFile {
+    seed: u64,
+    // name of the constants, see `GameConstants::preset`
+    preset: String,
+    route: Vec<(u64, Action)>,
+}
+
+/**
+ * Runs the command in `args` (program name excluded), returns the process exit code
+ */
+pub(crate) fn run(args: &[String]) -> i32 {
+    let (source, args) = match args {
+        [option, source, args @ ..] if option == "--constants" => (Some(source.as_str()), args),
+        [option] if option == "--constants" => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+        _ => (None, args),
+    };
+    // a replay picks the constants of its route
+    if let [command, route_file] = args {
+        if command == "replay" {
+            return exit_code(replay(source, route_file));
+        }
+    }
+    let constants = match source.map_or(Ok(GameConstants::default()), load_constants) {
+        Ok(constants) => constants,
+        Err(error) => {
+            eprintln!("{}", error);
+            return 1;
+        }
+    };
+    let result = match args.first().map(String::as_str) {
+        Some("balance") => balance(
+            &constants,
+            args.get(1)
+                .map_or(DEFAULT_REPORTS_DIRECTORY, String::as_str),
+        ),
+        Some("solve") => solve_route(
+            &constants,
+            args.get(1).map_or(DEFAULT_ROUTE_FILE, String::as_str),
+            args.get(2).map(String::as_str),
+        ),
+        _ => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+    exit_code(result)
+}
+
+fn exit_code(result: Result<(), String>) -> i32 {
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{}", error);
+            1
+        }
+    }
+}
+
+/// a preset name, or else a path to a TOML file
+fn load_constants(source: &str) -> Result<GameConstants, String> {
+    if let Some(preset) = Preset::from_name(source) {
+        return preset.constants();
+    }
+    let path = Path::new(source);
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("failed to read {}: {}", source, error))?;
+    let name = path
+        .file_stem()
+        .map_or(source.into(), |stem| stem.to_string_lossy());
+    parse_constants(&name, &content)
+}
+
+fn balance(constants: &GameConstants, directory: &str) -> Result<(), String> {
+    let files = write_reports(constants, Path::new(directory))
+        .map_err(|error| format!("failed to write reports in {}: {}", directory, error))?;
+    for file in files {
+        println!("{}", file.display());
+    }
+    Ok(())
+}
+
+/// quest named as in routes and reports, `Differentiation` when not given
+fn parse_quest(constants: &GameConstants, name: Option<&str>) -> Result<Research, String> {
+    let Some(name) = name else {
+        return Ok(Research::Differentiation);
+    };
+    quest_definitions(constants)
+        .into_iter()
+        .map(|definition| definition.research)
+        .find(|quest| format!("{:?}", quest) == name)
+        .ok_or_else(|| format!("unknown quest {}", name))
+}
+
+fn solve_route(
+    constants: &GameConstants,
+    route_file: &str,
+    goal: Option<&str>,
+) -> Result<(), String> {
+    let goal = parse_quest(constants, goal)?;
+    let solution = solve(constants, goal, SOLVER_MAX_SECONDS, SOLVER_BEAM_WIDTH);
+    println!("{:<16} {:>10} {:>10}", "quest", "reference", "solver");
+    for definition in quest_definitions(constants) {
+        println!(
+            "{:<16} {:>10} {:>10}",
+            format!("{:?}", definition.research),
+            format_seconds(solution.reference.completed(definition.research)),
+            format_seconds(solution.best.completed(definition.research))
+        );
+    }
+    if solution.plans.is_empty() {
+        println!("plans: the reference strategy could not be beaten");
+    } else {
+        println!("plans: {}", format_plans(&solution.plans));
+    }
+    for finding in degenerate_findings(&solution) {
+        println!("degenerate: {}", finding);
+    }
+    let route = serde_json::to_string(&RouteFile {
+        seed: solution.best.state.rng_seed,
+        preset: constants.preset.clone(),
+        route: solution.best.route,
+    })
+    .map_err(|error| error.to_string())?;
+    fs::write(route_file, route)
+        .map_err(|error| format!("failed to write {}: {}", route_file, error))?;
+    println!("route written to {}", route_file);
+    Ok(())
+}
+
+/**
+ * Plays the route of `route_file` with the seed and constants it was found with, `source` must
+ * give the same constants when they came from a TOML file
+ */
+fn replay(source: Option<&str>, route_file: &str) -> Result<(), String> {
+    let content = fs::read_to_string(route_file)
+        .map_err(|error| format!("failed to read {}: {}", route_file, error))?;
+    let RouteFile {
+        seed,
+        preset,
+        route,
+    } = serde_json::from_str(&content)
+        .map_err(|error| format!("invalid route in {}: {}", route_file, error))?;
+    let constants = match source {
+        Some(source) => load_constants(source)?,
+        None => Preset::from_name(&preset)
+            .ok_or_else(|| {
+                format!(
+                    "{} was found with the constants of {}, give them with --constants",
+                    route_file, preset
+                )
+            })?
+            .constants()?,
+    };
+    if constants.preset != preset {
+        return Err(format!(
+            "{} was found with the {} constants, not {}",
+            route_file, preset, constants.preset
+        ));
+    }
+    let last_tick = route.last().map_or(0, |(tick, _)| *tick);
+    let mut simulation = Simulation::new(constants, seed);
+    simulation.run(
+        &mut Replay::new(route),
+        Research::Differentiation,
+        (last_tick + 1) as f64 / TICKS_PER_SECOND,
+    );
+    for (quest, seconds) in &simulation.quests {
+        println!("{:<16} {:>10.1}", format!("{:?}", quest), seconds);
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use break_infinity::Decimal;
+
+/**
+ * Real-world codebase used as a unit of loc, sizes are orders of magnitude rather than exact counts
+ */
+pub(crate) struct Codebase {
+    pub name: &'static str,
+    // unit used when formatting loc, e.g. "1.50 Chromiums"
+    pub plural: &'static str,
+    pub loc: f64,
+    pub source: &'static str,
+}
+
+/**
+ * Sorted by size
+ */
+pub(crate) const CODEBASES: [Codebase; 8] = [
+    Codebase {
+        name: "Apollo 11 guidance computer",
+        plural: "Apollo 11s",
+        loc: 145e3,
+        source: "https://informationisbeautiful.net/visualizations/million-lines-of-code/",
+    },
+    Codebase {
+        name: "Space Shuttle flight software",
+        plural: "Space Shuttles",
+        loc: 400e3,
+        source: "https://informationisbeautiful.net/visualizations/million-lines-of-code/",
+    },
+    Codebase {
+        name: "Photoshop CS6",
+        plural: "Photoshops",
+        loc: 4.5e6,
+        source: "https://informationisbeautiful.net/visualizations/million-lines-of-code/",
+    },
+    Codebase {
+        name: "Linux Kernel 5.11",
+        plural: "Linux Kernels",
+        loc: 30e6,
+        source: "https://en.wikipedia.org/wiki/Linux_kernel",
+    },
+    Codebase {
+        name: "Chromium",
+        plural: "Chromiums",
+        loc: 35e6,
+        source: "https://openhub.net/p/chrome",
+    },
+    Codebase {
+        name: "Facebook",
+        plural: "Facebooks",
+        loc: 62e6,
+        source: "https://informationisbeautiful.net/visualizations/million-lines-of-code/",
+    },
+    Codebase {
+        name: "Google monorepo",
+        plural: "Google monorepos",
+        loc: 2e9,
+        source: "Potvin & Levenberg, Why Google Stores Billions of Lines of Code in a Single Repository, CACM 2016",
+    },
+    Codebase {
+        name: "all of GitHub",
+        plural: "GitHubs",
+        loc: 1e12,
+        source: "rough estimate from the hundreds of millions of repositories hosted on GitHub",
+    },
+];
+
+/**
+ * Largest codebase not bigger than `loc`
+ */
+pub(crate) fn current_codebase(loc: Decimal) -> Option<&'static Codebase> {
+    CODEBASES
+        .iter()
+        .rev()
+        .find(|codebase| loc.abs() >= Decimal::new(codebase.loc))
+}
+
+/**
+ * Smallest codebase bigger than `loc`, None once all of GitHub has been written
+ */
+pub(crate) fn next_codebase(loc: Decimal) -> Option<&'static Codebase> {
+    CODEBASES
+        .iter()
+        .find(|codebase| loc < Decimal::new(codebase.loc))
+}
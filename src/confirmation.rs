@@ -0,0 +1,263 @@
+#![allow(non_snake_case)]
+use crate::actions::Action;
+use crate::constants::{use_game_constants, GameConstants};
+use crate::layoffs::LayoffPreview;
+use crate::modifiers::Modifier;
+use crate::simple_logs::LogCategory;
+use crate::snapshot::Snapshot;
+use crate::state::{use_game_state, State};
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::Writable;
+use dioxus::prelude::*;
+
+/**
+ * Last destructive action, undoable until `expires_at` (game time in seconds)
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Undo {
+    pub action: Action,
+    // what the action changed, taken back out by undo
+    pub changes: Snapshot,
+    // added by the action, such as the low morale after layoffs
+    pub modifiers: Vec<Modifier>,
+    pub expires_at: f64,
+}
+
+/**
+ * Asks for a confirmation before destructive actions when the setting is on, performs others
+ */
+pub(crate) fn request_action(state: &mut State, constants: &GameConstants, action: Action) -> bool {
+    if action.is_destructive() && state.settings.confirm_destructive_actions {
+        if !action.is_available(state, constants) {
+            return false;
+        }
+        state.pending_confirmation = Some(action);
+        return true;
+    }
+    perform_with_undo(state, constants, action)
+}
+
+/**
+ * `request_action` for buttons: logs the action once performed, not when asking to confirm it
+ */
+pub(crate) fn click_action(state: &mut State, constants: &GameConstants, action: Action) {
+    if request_action(state, constants, action) && state.pending_confirmation != Some(action) {
+        state.logs.log(LogCategory::Action, &action.name());
+    }
+}
+
+/**
+ * Applies `action`, keeping what it changed for a while if it is destructive
+ */
+pub(crate) fn perform_with_undo(
+    state: &mut State,
+    constants: &GameConstants,
+    action: Action,
+) -> bool {
+    let snapshot = Snapshot::take(state, action.affected_fields());
+    let modifiers_before = state.modifiers.iter().count();
+    if !action.apply(state, constants) {
+        return false;
+    }
+    if action.is_destructive() {
+        state.undo = Some(Undo {
+            action,
+            changes: snapshot.changes_since(state),
+            modifiers: state
+                .modifiers
+                .iter()
+                .skip(modifiers_before)
+                .cloned()
+                .collect(),
+            expires_at: state.game_time + constants.undo_window_seconds.to_number(),
+        });
+    }
+    true
+}
+
+/**
+ * Takes back what the last destructive action changed, if still in the undo window.
+ * Loc written or spent and devs hired since are kept: undoing never refunds a purchase.
+ */
+pub(crate) fn undo(state: &mut State) -> bool {
+    let Some(undo) = state.undo.take() else {
+        return false;
+    };
+    if !undo.changes.revert_changes(state) {
+        state.logs.log(
+            LogCategory::Action,
+            &format!("cannot undo {}, what it gave was spent", undo.action.name()),
+        );
+        state.undo = Some(undo);
+        return false;
+    }
+    for modifier in &undo.modifiers {
+        state.modifiers.remove(modifier);
+    }
+    state
+        .logs
+        .log(LogCategory::Action, &format!("undo {}", undo.action.name()));
+    true
+}
+
+#[component]
+pub(crate) fn ConfirmationModal() -> Element {
+    let mut state = use_game_state();
+    let constants = use_game_constants();
+    let action = state.read().pending_confirmation?;
+    let undo_window = constants.undo_window_seconds;
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            div {
+                class: "modal",
+                button {
+                    class: "modal-close",
+                    onclick: move |_| state.write().pending_confirmation = None,
+                    "×"
+                }
+                h3 {"{action.name()}?"}
+                if action == Action::SmartStaffing {
+                    LayoffPreview {}
+                }
+                p {"This cannot be reverted after {undo_window} seconds."}
+                button {
+                    class: "repeatable-action-button",
+                    onclick: move |_| {
+                        let mut state = state.write();
+                        state.pending_confirmation = None;
+                        if perform_with_undo(&mut state, &constants, action) {
+                            state
+                                .logs
+                                .log(LogCategory::Action, &format!("confirmed {}", action.name()));
+                        } else {
+                            state
+                                .logs
+                                .log(LogCategory::Action, &format!("cannot {} anymore", action.name()));
+                        }
+                    },
+                    "confirm"
+                }
+                button {
+                    class: "repeatable-action-button",
+                    onclick: move |_| state.write().pending_confirmation = None,
+                    "cancel"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub(crate) fn UndoBanner() -> Element {
+    let mut state = use_game_state();
+    let (name, remaining) = {
+        let state = state.read();
+        let undo = state.undo.as_ref()?;
+        (
+            undo.action.name(),
+            (undo.expires_at - state.game_time).ceil(),
+        )
+    };
+    rsx! {
+        div {
+            class: "undo-banner",
+            span {"{name} done, {remaining}s left to "}
+            button {
+                onclick: move |_| {
+                    undo(&mut state.write());
+                },
+                "undo"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::Research;
+    use crate::layoffs::LayoffPlan;
+    use crate::producers::Producer;
+    use break_infinity::Decimal;
+
+    #[test]
+    fn confirmed_rmrf_can_be_undone_until_it_expires() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.researched.insert(Research::Rmrf);
+        state.loc = Decimal::new(1e6);
+        state.bugs = Decimal::new(1e3);
+
+        assert!(request_action(&mut state, &constants, Action::Rmrf));
+        assert_eq!(state.pending_confirmation, Some(Action::Rmrf));
+        assert_eq!(state.loc, Decimal::new(1e6));
+
+        state.pending_confirmation = None;
+        assert!(perform_with_undo(&mut state, &constants, Action::Rmrf));
+        assert_eq!(state.loc, Decimal::ZERO);
+        assert!(undo(&mut state));
+        assert_eq!(state.loc, Decimal::new(1e6));
+        assert_eq!(state.bugs, Decimal::new(1e3));
+        assert!(!undo(&mut state));
+
+        perform_with_undo(&mut state, &constants, Action::Rmrf);
+        state.game_time += constants.undo_window_seconds.to_number();
+        state.update(Decimal::new(10.0));
+        assert!(state.undo.is_none());
+    }
+    #[test]
+    fn undo_keeps_what_happened_since() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state
+            .researched
+            .extend([Research::Internship, Research::SmartStaffing]);
+        state.interns = Decimal::new(100.0);
+        state.loc = Decimal::new(1e6);
+        let severance = LayoffPlan::new(&state, &constants).severance;
+
+        assert!(perform_with_undo(
+            &mut state,
+            &constants,
+            Action::SmartStaffing
+        ));
+        let laid_off = Decimal::new(100.0) - state.interns;
+        assert!(laid_off > Decimal::ZERO);
+        // the loc left after the severance is spent, then some is written
+        assert!(Action::HireMax(Producer::Interns).apply(&mut state, &constants));
+        let hired = state.manual_interns;
+        state.loc += Decimal::new(10.0);
+        let loc = state.loc;
+
+        assert!(undo(&mut state));
+        assert_eq!(state.loc, loc + severance);
+        assert_eq!(state.interns, Decimal::new(100.0));
+        assert_eq!(state.manual_interns, hired);
+    }
+
+    #[test]
+    fn undo_never_gives_back_spent_loc() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state
+            .researched
+            .extend([Research::Rmrf, Research::GitCommit, Research::GitRevert]);
+        state.loc = Decimal::new(1e6);
+        assert!(Action::GitCommit.apply(&mut state, &constants));
+        assert!(Action::Rmrf.apply(&mut state, &constants));
+        assert!(perform_with_undo(
+            &mut state,
+            &constants,
+            Action::GitRevert(0)
+        ));
+        // the reverted loc is spent before undoing
+        let reverted = state.loc;
+        state.spend(reverted);
+
+        assert!(!undo(&mut state));
+        assert_eq!(state.loc, Decimal::ZERO);
+        assert!(state.undo.is_some());
+    }
+}
@@ -0,0 +1,276 @@
+#![allow(non_snake_case)]
+//! Developer console: text commands manipulating the state at runtime, echoed to the logs.
+
+use crate::constants::{use_game_constants, GameConstants, Research};
+use crate::research_data::apply_research_effect;
+use crate::simple_logs::LogCategory;
+use crate::snapshot::StateField;
+use crate::state::{use_game_state, State};
+use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+
+const COMMANDS: [&str; 6] = ["set", "grant", "tick", "reset", "dump", "help"];
+const HELP: [&str; 6] = [
+    "set <field> <number>: sets a state field, 1e15 notation accepted",
+    "grant research <Research>: researches without paying, effects included",
+    "tick <count>: runs that many game updates at the current tick rate",
+    "reset: starts a new game, keeping settings, theme and speedrun history",
+    "dump: logs every state field and the researches",
+    "help: logs this list",
+];
+// beyond that, the page freezes for too long
+const MAX_TICKS: u64 = 100_000;
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Set(StateField, Decimal),
+    Grant(Research),
+    Tick(u64),
+    Reset,
+    Dump,
+    Help,
+}
+
+fn parse_field(name: &str) -> Result<StateField, String> {
+    StateField::ALL
+        .into_iter()
+        .find(|field| field.name() == name)
+        .ok_or_else(|| format!("unknown field {}", name))
+}
+
+fn parse_research(name: &str) -> Result<Research, String> {
+    Research::ALL
+        .into_iter()
+        .find(|research| format!("{:?}", research) == name)
+        .ok_or_else(|| format!("unknown research {}", name))
+}
+
+fn parse(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["set", field, value] => {
+            let value = value
+                .parse::<Decimal>()
+                .map_err(|_| format!("{} is not a number", value))?;
+            Ok(Command::Set(parse_field(field)?, value))
+        }
+        ["grant", "research", research] => Ok(Command::Grant(parse_research(research)?)),
+        ["tick", count] => match count.parse::<u64>() {
+            Ok(count) if count <= MAX_TICKS => Ok(Command::Tick(count)),
+            _ => Err(format!("tick count must be between 0 and {}", MAX_TICKS)),
+        },
+        ["reset"] => Ok(Command::Reset),
+        ["dump"] => Ok(Command::Dump),
+        ["help"] => Ok(Command::Help),
+        [] => Err("type help for the list of commands".to_string()),
+        [command, ..] if COMMANDS.contains(command) => {
+            Err(format!("wrong arguments for {}, type help", command))
+        }
+        [command, ..] => Err(format!("unknown command {}, type help", command)),
+    }
+}
+
+/**
+ * Applies `command`, returns the lines to log
+ */
+fn execute(command: Command, state: &mut State, constants: &GameConstants) -> Vec<String> {
+    match command {
+        Command::Set(field, value) => {
+            *field.get_mut(state) = value;
+            vec![format!("{} = {}", field.name(), value)]
+        }
+        Command::Grant(research) => {
+            if !state.researched.insert(research) {
+                return vec![format!("{:?} already researched", research)];
+            }
+            apply_research_effect(&research, state, constants);
+            vec![format!("granted {:?}", research)]
+        }
+        Command::Tick(count) => {
+            // same rate as the game loop
+            let dt_seconds = Decimal::new(1e3 / state.settings.tick_milliseconds as f64);
+            for _ in 0..count {
+                state.update(dt_seconds);
+            }
+            vec![format!(
+                "ran {} ticks, game time {:.1}s",
+                count, state.game_time
+            )]
+        }
+        Command::Reset => {
+            let mut new_game = State::new(constants.clone());
+            new_game.settings = state.settings.clone();
+            new_game.theme = state.theme;
+            new_game.speedrun_history = std::mem::take(&mut state.speedrun_history);
+            new_game.logs = state.logs.clone();
+            new_game.logs.set_game_time(0.0);
+            *state = new_game;
+            vec![format!("new game started, random seed {}", state.rng_seed)]
+        }
+        Command::Dump => {
+            let mut researched: Vec<String> = state
+                .researched
+                .iter()
+                .map(|research| format!("{:?}", research))
+                .collect();
+            researched.sort();
+            StateField::ALL
+                .into_iter()
+                .map(|field| format!("{} = {}", field.name(), field.get(state)))
+                .chain([
+                    format!("game_time = {:.1}", state.game_time),
+                    format!("dt = {}", state.dt),
+                    format!("researched = {}", researched.join(", ")),
+                ])
+                .collect()
+        }
+        Command::Help => HELP.iter().map(|line| line.to_string()).collect(),
+    }
+}
+
+/**
+ * Runs a console line, echoing it and its outcome to the logs
+ */
+fn run(line: &str, state: &mut State, constants: &GameConstants) {
+    let output = match parse(line) {
+        Ok(command) => execute(command, state, constants),
+        Err(error) => vec![error],
+    };
+    state
+        .logs
+        .log(LogCategory::Cheat, &format!("> {}", line.trim()));
+    for line in output {
+        state.logs.log(LogCategory::Cheat, &line);
+    }
+}
+
+/**
+ * Whole lines the input may become once its last word is complete
+ */
+fn completions(line: &str) -> Vec<String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    // a trailing space starts a new word
+    let (done, current) = match (words.split_last(), line.ends_with(char::is_whitespace)) {
+        (Some((last, done)), false) => (done, *last),
+        _ => (words.as_slice(), ""),
+    };
+    let candidates: Vec<String> = match done {
+        [] => COMMANDS.iter().map(|command| command.to_string()).collect(),
+        ["set"] => StateField::ALL
+            .iter()
+            .map(|field| field.name().to_string())
+            .collect(),
+        ["grant"] => vec!["research".to_string()],
+        ["grant", "research"] => Research::ALL
+            .iter()
+            .map(|research| format!("{:?}", research))
+            .collect(),
+        _ => Vec::new(),
+    };
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(current) && candidate != current)
+        .map(|candidate| {
+            done.iter()
+                .copied()
+                .chain([candidate.as_str()])
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+#[component]
+pub(crate) fn DevConsole() -> Element {
+    let mut state = use_game_state();
+    let constants = use_game_constants();
+    let mut line = use_signal(String::new);
+    rsx! {
+        if state.read().researched.contains(&Research::Cheating) {
+            form {
+                class: "dev-console",
+                onsubmit: move |_| {
+                    let submitted = line.take();
+                    run(&submitted, &mut state.write(), &constants);
+                },
+                input {
+                    r#type: "text",
+                    list: "dev-console-completions",
+                    placeholder: "dev console, type help",
+                    value: "{line}",
+                    oninput: move |event: Event<FormData>| line.set(event.value()),
+                }
+                datalist {
+                    id: "dev-console-completions",
+                    for completion in completions(&line.read()) {
+                        option { value: "{completion}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_parsed_against_fields_and_researches() {
+        assert_eq!(
+            parse("set loc 1e15"),
+            Ok(Command::Set(StateField::Loc, Decimal::new(1e15)))
+        );
+        assert_eq!(
+            parse(" grant  research HumanResources "),
+            Ok(Command::Grant(Research::HumanResources))
+        );
+        assert_eq!(parse("tick 10000"), Ok(Command::Tick(10_000)));
+        assert_eq!(parse("set lines 3"), Err("unknown field lines".to_string()));
+        assert_eq!(
+            parse("set loc lots"),
+            Err("lots is not a number".to_string())
+        );
+        assert!(parse("tick 1000000").is_err());
+        assert!(parse("grant HumanResources").is_err());
+    }
+
+    #[test]
+    fn commands_change_the_state_and_log_their_outcome() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.settings.tick_milliseconds = 100;
+        run("set interns 10", &mut state, &constants);
+        run("grant research InternsPromotion", &mut state, &constants);
+        run("tick 10", &mut state, &constants);
+        assert!(state.researched.contains(&Research::InternsPromotion));
+        assert!(state.loc > Decimal::ZERO);
+        assert!((state.game_time - 1.0).abs() < 1e-9);
+
+        run("reset", &mut state, &constants);
+        assert_eq!(state.loc, Decimal::ZERO);
+        assert_eq!(state.settings.tick_milliseconds, 100);
+        let logs: Vec<String> = state
+            .logs
+            .entries()
+            .map(|entry| entry.message.clone())
+            .collect();
+        assert!(logs.contains(&"> set interns 10".to_string()));
+        assert!(logs.contains(&"granted InternsPromotion".to_string()));
+        assert!(logs.contains(&format!("new game started, random seed {}", state.rng_seed)));
+    }
+
+    #[test]
+    fn last_word_is_completed() {
+        assert_eq!(completions("gr"), vec!["grant"]);
+        assert_eq!(completions("grant "), vec!["grant research"]);
+        assert_eq!(
+            completions("grant research Hu"),
+            vec!["grant research HumanBrain", "grant research HumanResources"]
+        );
+        assert_eq!(completions("set manual_j"), vec!["set manual_junior_devs"]);
+        assert!(completions("set loc 1").is_empty());
+    }
+}
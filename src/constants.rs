@@ -1,7 +1,19 @@
+use crate::presets::Preset;
 use break_infinity::Decimal;
+use dioxus::prelude::use_context;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::OnceLock;
 
-#[derive(Clone, Debug, PartialEq)]
+/**
+ * Balance of the game, loaded from a preset, see `presets`
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub(crate) struct GameConstants {
+    // preset or file these constants come from
+    #[serde(skip_deserializing)]
+    pub preset: String,
     // interns recruitment cost
     pub interns_loc_base_cost: Decimal,
     pub interns_loc_growth_rate: Decimal,
@@ -34,6 +46,9 @@ pub(crate) struct GameConstants {
     pub research_management_career_loc_cost: Decimal,
     pub research_smart_staffing_loc_cost: Decimal,
     pub research_recursive_hr_loc_cost: Decimal,
+    pub research_git_commit_loc_cost: Decimal,
+    pub research_git_revert_loc_cost: Decimal,
+    pub research_hiring_quotas_loc_cost: Decimal,
     // initial promotion ratio
     pub interns_promotion_ratio_dt: Decimal,
     pub junior_devs_promotion_ratio_dt: Decimal,
@@ -51,6 +66,8 @@ pub(crate) struct GameConstants {
     pub hrs_senior_devs_quota: Decimal,
     pub hrs_hrs_dt: Decimal,
     pub hrs_hrs_quota: Decimal,
+    pub hrs_pms_dt: Decimal,
+    pub hrs_pms_quota: Decimal,
     // pm bugs conversion
     pub pms_bugs_conversion_dt: Decimal,
     // manual loc production
@@ -82,79 +99,177 @@ pub(crate) struct GameConstants {
     // multipliers
     pub research_syntax_coloring_multiplier: Decimal,
     pub senior_devs_management_career_ratio: Decimal,
+    // random events
+    pub random_events_min_interval_seconds: Decimal,
+    pub random_events_max_interval_seconds: Decimal,
+    pub event_production_outage_loc_seconds: Decimal,
+    pub event_production_outage_features_loss_ratio: Decimal,
+    pub event_intern_deletes_prod_loss_ratio: Decimal,
+    pub event_conference_talk_hiring_multiplier: Decimal,
+    pub event_conference_talk_duration_seconds: Decimal,
+    pub event_viral_launch_loc_seconds: Decimal,
+    pub event_viral_launch_features_ratio: Decimal,
+    pub event_viral_launch_bugs_ratio: Decimal,
+    pub event_audit_loc_seconds: Decimal,
+    pub event_audit_bugs_ratio: Decimal,
+    // smart staffing: share of the devs laid off, severance as a share of each one's base
+    // hiring cost, bugs ratio increase per share of devs laid off while morale recovers
+    pub smart_staffing_layoff_ratio: Decimal,
+    pub layoff_severance_ratio: Decimal,
+    pub layoff_morale_bugs_ratio: Decimal,
+    pub layoff_morale_duration_seconds: Decimal,
+    // share of a checkpoint loc lost when reverting to it
+    pub git_revert_loc_loss_ratio: Decimal,
+    // game time during which a destructive action can be undone
+    pub undo_window_seconds: Decimal,
     pub dt: Decimal,
 }
 
+/**
+ * Constants of the running game, provided to every view by the `Game` layout
+ */
+pub(crate) fn use_game_constants() -> GameConstants {
+    use_context()
+}
+
 impl Default for GameConstants {
+    /// the normal preset, parsed once: new games and loaded saves start from it
     fn default() -> Self {
-        GameConstants {
-            interns_loc_base_cost: Decimal::new(20.0),
-            interns_loc_growth_rate: Decimal::new(1.015),
-            junior_devs_loc_base_cost: Decimal::new(1250.0),
-            junior_devs_loc_growth_rate: Decimal::new(1.02),
-            senior_devs_loc_base_cost: Decimal::new(15_000.0),
-            senior_devs_loc_growth_rate: Decimal::new(1.0175),
-            hrs_loc_base_cost: Decimal::new(15_000.0),
-            hrs_loc_growth_rate: Decimal::new(1.0175),
-            pms_loc_base_cost: Decimal::new(15_000.0),
-            pms_loc_growth_rate: Decimal::new(1.0175),
-            research_internship_loc_cost: Decimal::new(1.0),
-            research_junior_devs_position_loc_cost: Decimal::new(1.0),
-            research_senior_devs_position_loc_cost: Decimal::new(1.0),
-            research_human_resources_loc_cost: Decimal::new(1.0),
-            research_project_management_loc_cost: Decimal::new(1.0),
-            research_interns_promotion_loc_cost: Decimal::new(1.0),
-            research_junior_devs_promotion_loc_cost: Decimal::new(1.0),
-            research_code_metrics_loc_cost: Decimal::new(1.0),
-            research_speedrun_loc_cost: Decimal::new(1.0),
-            research_logs_loc_cost: Decimal::new(1.0),
-            research_rmrf_loc_cost: Decimal::new(1.0),
-            research_toggle_theme_loc_cost: Decimal::new(1.0),
-            research_syntax_coloring_multiplier_loc_cost: Decimal::new(1.0),
-            research_management_career_loc_cost: Decimal::new(1.0),
-            research_smart_staffing_loc_cost: Decimal::new(1.0),
-            research_recursive_hr_loc_cost: Decimal::new(1.0),
-            interns_promotion_ratio_dt: Decimal::new(0.04),
-            junior_devs_promotion_ratio_dt: Decimal::new(0.02),
-            senior_devs_retirement_ratio_dt: Decimal::new(0.01),
-            interns_loc_dt: Decimal::new(1.0),
-            junior_devs_loc_dt: Decimal::new(40.0),
-            senior_devs_loc_dt: Decimal::new(1000.0),
-            hrs_interns_dt: Decimal::new(1e-2),
-            hrs_interns_quota: Decimal::new(0.90),
-            hrs_junior_devs_dt: Decimal::new(1e-3),
-            hrs_junior_devs_quota: Decimal::new(0.09),
-            hrs_senior_devs_dt: Decimal::new(1e-4),
-            hrs_senior_devs_quota: Decimal::new(0.01),
-            hrs_hrs_dt: Decimal::new(1e-4),
-            hrs_hrs_quota: Decimal::new(0.01),
-            pms_bugs_conversion_dt: Decimal::new(1.0),
-            loc_per_clicks: Decimal::new(1.0),
-            debug_per_clicks: Decimal::new(1.0),
-            manual_bugs_ratio: Decimal::new(1.0),
-            interns_bugs_ratio: Decimal::new(2.0),
-            junior_devs_bugs_ratio: Decimal::new(1.5),
-            senior_devs_bugs_ratio: Decimal::new(1.0),
-            quest_hello_world_loc_cost: Decimal::new(1.0),
-            quest_fizz_buzz_loc_cost: Decimal::new(10.0),
-            quest_calculator_loc_cost: Decimal::new(100.0),
-            quest_game_of_life_loc_cost: Decimal::new(1e3),
-            quest_text_editor_loc_cost: Decimal::new(1e4),
-            quest_physics_engine_loc_cost: Decimal::new(1e5),
-            quest_bacteria_loc_cost: Decimal::new(1e6),
-            quest_browser_loc_cost: Decimal::new(5e6),
-            quest_kernel_loc_cost: Decimal::new(30e6),
-            quest_mouse_loc_cost: Decimal::new(100e6),
-            quest_human_brain_loc_cost: Decimal::new(1e9),
-            quest_economy_loc_cost: Decimal::new(10e9),
-            quest_climate_loc_cost: Decimal::new(100e9),
-            quest_earth_loc_cost: Decimal::new(1e12),
-            quest_solar_system_loc_cost: Decimal::new(1e15),
-            quest_universe_loc_cost: Decimal::new(1e18),
-            quest_differentiation_loc_cost: Decimal::new(1e21),
-            research_syntax_coloring_multiplier: Decimal::new(2.0),
-            senior_devs_management_career_ratio: Decimal::new(0.5),
-            dt: Decimal::new(0.01),
+        static NORMAL: OnceLock<GameConstants> = OnceLock::new();
+        NORMAL
+            .get_or_init(|| {
+                Preset::Normal
+                    .constants()
+                    .expect("the normal preset is valid, see presets tests")
+            })
+            .clone()
+    }
+}
+
+/// false for NaN
+fn in_range(value: &Decimal, min: &Decimal, max: &Decimal) -> bool {
+    matches!(
+        value.partial_cmp(min),
+        Some(Ordering::Greater | Ordering::Equal)
+    ) && matches!(
+        value.partial_cmp(max),
+        Some(Ordering::Less | Ordering::Equal)
+    )
+}
+
+impl GameConstants {
+    /**
+     * Every balance mistake that would break the game, not only the first one.
+     * Bugs ratios are bugs per loc rather than fractions, they are only required to be positive,
+     * as are the layoff costs.
+     */
+    pub(crate) fn validate(&self) -> Result<(), Vec<String>> {
+        let growth_rates = [
+            ("interns_loc_growth_rate", &self.interns_loc_growth_rate),
+            (
+                "junior_devs_loc_growth_rate",
+                &self.junior_devs_loc_growth_rate,
+            ),
+            (
+                "senior_devs_loc_growth_rate",
+                &self.senior_devs_loc_growth_rate,
+            ),
+            ("hrs_loc_growth_rate", &self.hrs_loc_growth_rate),
+            ("pms_loc_growth_rate", &self.pms_loc_growth_rate),
+        ];
+        let ratios = [
+            (
+                "interns_promotion_ratio_dt",
+                &self.interns_promotion_ratio_dt,
+            ),
+            (
+                "junior_devs_promotion_ratio_dt",
+                &self.junior_devs_promotion_ratio_dt,
+            ),
+            (
+                "senior_devs_retirement_ratio_dt",
+                &self.senior_devs_retirement_ratio_dt,
+            ),
+            ("hrs_interns_quota", &self.hrs_interns_quota),
+            ("hrs_junior_devs_quota", &self.hrs_junior_devs_quota),
+            ("hrs_senior_devs_quota", &self.hrs_senior_devs_quota),
+            ("hrs_hrs_quota", &self.hrs_hrs_quota),
+            ("hrs_pms_quota", &self.hrs_pms_quota),
+            (
+                "senior_devs_management_career_ratio",
+                &self.senior_devs_management_career_ratio,
+            ),
+            (
+                "event_production_outage_features_loss_ratio",
+                &self.event_production_outage_features_loss_ratio,
+            ),
+            (
+                "event_intern_deletes_prod_loss_ratio",
+                &self.event_intern_deletes_prod_loss_ratio,
+            ),
+            (
+                "event_viral_launch_features_ratio",
+                &self.event_viral_launch_features_ratio,
+            ),
+            (
+                "event_viral_launch_bugs_ratio",
+                &self.event_viral_launch_bugs_ratio,
+            ),
+            ("event_audit_bugs_ratio", &self.event_audit_bugs_ratio),
+            ("git_revert_loc_loss_ratio", &self.git_revert_loc_loss_ratio),
+            (
+                "smart_staffing_layoff_ratio",
+                &self.smart_staffing_layoff_ratio,
+            ),
+        ];
+        let positives = [
+            ("manual_bugs_ratio", &self.manual_bugs_ratio),
+            ("interns_bugs_ratio", &self.interns_bugs_ratio),
+            ("junior_devs_bugs_ratio", &self.junior_devs_bugs_ratio),
+            ("senior_devs_bugs_ratio", &self.senior_devs_bugs_ratio),
+            ("layoff_severance_ratio", &self.layoff_severance_ratio),
+            ("layoff_morale_bugs_ratio", &self.layoff_morale_bugs_ratio),
+            (
+                "layoff_morale_duration_seconds",
+                &self.layoff_morale_duration_seconds,
+            ),
+        ];
+        let mut errors = Vec::new();
+        for (name, value) in growth_rates {
+            if value.partial_cmp(&Decimal::ONE) != Some(Ordering::Greater) {
+                errors.push(format!("{} must be greater than 1, not {}", name, value));
+            }
+        }
+        for (name, value) in ratios {
+            if !in_range(value, &Decimal::ZERO, &Decimal::ONE) {
+                errors.push(format!("{} must be between 0 and 1, not {}", name, value));
+            }
+        }
+        for (name, value) in positives {
+            if !matches!(
+                value.partial_cmp(&Decimal::ZERO),
+                Some(Ordering::Greater | Ordering::Equal)
+            ) {
+                errors.push(format!("{} must be positive, not {}", name, value));
+            }
+        }
+        if !in_range(
+            &self.random_events_min_interval_seconds,
+            &Decimal::ZERO,
+            &self.random_events_max_interval_seconds,
+        ) {
+            errors.push(format!(
+                "random_events_min_interval_seconds must be between 0 and random_events_max_interval_seconds, not {}",
+                self.random_events_min_interval_seconds
+            ));
+        }
+        if self.dt.partial_cmp(&Decimal::ZERO) != Some(Ordering::Greater) {
+            errors.push(format!("dt must be greater than 0, not {}", self.dt));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 }
@@ -162,7 +277,7 @@ impl Default for GameConstants {
 /**
  *For optimization purpose: avoid using String when all research names are known ahead of time
  */
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 #[repr(u32)]
 pub(crate) enum Research {
     Bacteria,
@@ -176,7 +291,10 @@ pub(crate) enum Research {
     Economy,
     FizzBuzz,
     GameOfLife,
+    GitCommit,
+    GitRevert,
     HelloWorld,
+    HiringQuotas,
     HumanBrain,
     HumanResources,
     Internship,
@@ -200,3 +318,46 @@ pub(crate) enum Research {
     ToggleTheme,
     Universe,
 }
+
+impl Research {
+    #[cfg(feature = "cheats")]
+    pub(crate) const ALL: [Research; 37] = [
+        Research::Bacteria,
+        Research::Browser,
+        Research::Calculator,
+        Research::Cheating,
+        Research::Climate,
+        Research::CodeMetrics,
+        Research::Differentiation,
+        Research::Earth,
+        Research::Economy,
+        Research::FizzBuzz,
+        Research::GameOfLife,
+        Research::GitCommit,
+        Research::GitRevert,
+        Research::HelloWorld,
+        Research::HiringQuotas,
+        Research::HumanBrain,
+        Research::HumanResources,
+        Research::Internship,
+        Research::InternsPromotion,
+        Research::JuniorDevsPosition,
+        Research::JuniorDevsPromotion,
+        Research::Kernel,
+        Research::Logs,
+        Research::ManagementCareer,
+        Research::Mouse,
+        Research::PhysicsEngine,
+        Research::ProjectManagement,
+        Research::RecursiveHR,
+        Research::Rmrf,
+        Research::SeniorDevsPosition,
+        Research::SmartStaffing,
+        Research::SolarSystem,
+        Research::Speedrun,
+        Research::SyntaxColoringMultiplier,
+        Research::TextEditor,
+        Research::ToggleTheme,
+        Research::Universe,
+    ];
+}
@@ -0,0 +1,38 @@
+use crate::format_duration::format_duration;
+use crate::state::State;
+use break_infinity::Decimal;
+
+/**
+ * Seconds until `loc` reaches `loc_cost`, at the current LOC/s plus the LOC/s projected from HR hiring.
+ * Zero if already affordable, None if it will never be at the current rates.
+ */
+pub(crate) fn seconds_until_affordable(loc_cost: Decimal, state: &State) -> Option<f64> {
+    if state.loc >= loc_cost {
+        return Some(0.0);
+    }
+    let loc_dt = state.loc_dt.max(&Decimal::ZERO);
+    let loc_dt_growth = state.loc_dt_growth.max(&Decimal::ZERO);
+    if loc_dt <= Decimal::ZERO && loc_dt_growth <= Decimal::ZERO {
+        return None;
+    }
+    // solve loc + loc_dt * t + loc_dt_growth * t^2 / 2 = loc_cost
+    // written so that it stays accurate when loc_dt_growth is negligible
+    let missing = loc_cost - state.loc;
+    let two = Decimal::new(2.0);
+    let seconds = missing * two / (loc_dt + (loc_dt.sqr() + loc_dt_growth * missing * two).sqrt());
+    Some(seconds.to_number())
+}
+
+pub(crate) fn format_eta(seconds: Option<f64>) -> String {
+    match seconds {
+        Some(seconds) if seconds <= 0.0 => "affordable now".to_string(),
+        Some(seconds) => format!("affordable in {}", format_duration(seconds)),
+        None => "not affordable at current rate".to_string(),
+    }
+}
+
+/// seconds as written in balance reports and golden files, - when never
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn format_seconds(seconds: Option<f64>) -> String {
+    seconds.map_or_else(|| "-".to_string(), |seconds| format!("{:.1}", seconds))
+}
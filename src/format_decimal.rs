@@ -1,49 +1,160 @@
+#![allow(non_snake_case)]
+use crate::codebases::current_codebase;
+use crate::state::use_game_state;
 use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use dioxus::prelude::Writable;
+use serde::{Deserialize, Serialize};
 
-fn format_decimal(decimal: Decimal, unit: &str) -> String {
-    let stages: Vec<(Decimal, String)> = vec![
-        (Decimal::new(1e3), "".to_string()),
-        (Decimal::new(1e6), "Kilo".to_string()),
-        (Decimal::new(1e9), "Mega".to_string()),
-        (Decimal::new(1e12), "Giga".to_string()),
-        (Decimal::new(1e15), "Tera".to_string()),
-        (Decimal::new(1e18), "Peta".to_string()),
-        (Decimal::new(1e21), "Exa".to_string()),
+/**
+ * How big numbers are displayed, picked by the player and stored in `State`
+ */
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[repr(u8)]
+pub(crate) enum Notation {
+    // SI prefixes, loc in Linux Kernels
+    #[default]
+    LinuxKernels,
+    Si,
+    Scientific,
+    Engineering,
+    Letters,
+    // SI prefixes, loc in real-world codebases
+    Codebases,
+}
+
+impl Notation {
+    pub(crate) const ALL: [Notation; 6] = [
+        Notation::LinuxKernels,
+        Notation::Si,
+        Notation::Scientific,
+        Notation::Engineering,
+        Notation::Letters,
+        Notation::Codebases,
     ];
-    for (limit, symbol) in stages {
-        if decimal.abs() < limit {
-            return format!(
-                "{} {}{}",
-                (decimal / (limit / Decimal::new(1e3))).to_fixed(2),
-                symbol,
-                unit
-            );
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Notation::LinuxKernels => "Linux Kernels",
+            Notation::Si => "SI",
+            Notation::Scientific => "scientific",
+            Notation::Engineering => "engineering",
+            Notation::Letters => "letters",
+            Notation::Codebases => "codebases",
         }
     }
-    format!("{} {}", decimal.to_precision(2), unit)
 }
 
-pub(crate) fn format_decimal_devs(decimal: Decimal) -> String {
-    format_decimal(decimal, "devs")
+const SI_PREFIXES: [&str; 11] = [
+    "", "Kilo", "Mega", "Giga", "Tera", "Peta", "Exa", "Zetta", "Yotta", "Ronna", "Quetta",
+];
+
+// short scale, followed by aa, ab... zz
+const LETTERS_SUFFIXES: [&str; 5] = ["", "K", "M", "B", "T"];
+
+/**
+ * Splits a non zero decimal into a mantissa and an exponent multiple of `step`,
+ * the mantissa rounded to 2 decimals is in [1, 10^step[
+ */
+fn split_exponent(decimal: Decimal, step: i64) -> (f64, i64) {
+    let mut exponent = (decimal.abs_log10().floor() as i64).div_euclid(step) * step;
+    let mut mantissa = (decimal / Decimal::pow10(exponent as f64)).to_number();
+    if (mantissa.abs() * 100.0).round() / 100.0 >= 10f64.powi(step as i32) {
+        exponent += step;
+        mantissa /= 10f64.powi(step as i32);
+    }
+    (mantissa, exponent)
+}
+
+fn format_exponent(decimal: Decimal, unit: &str, step: i64) -> String {
+    let (mantissa, exponent) = split_exponent(decimal, step);
+    format!("{:.2}e{} {}", mantissa, exponent, unit)
+}
+
+fn format_si(decimal: Decimal, unit: &str) -> String {
+    let (mantissa, exponent) = split_exponent(decimal, 3);
+    match SI_PREFIXES.get((exponent / 3) as usize) {
+        Some(prefix) => format!("{:.2} {}{}", mantissa, prefix, unit),
+        None => format_exponent(decimal, unit, 1),
+    }
+}
+
+fn format_letters(decimal: Decimal, unit: &str) -> String {
+    let (mantissa, exponent) = split_exponent(decimal, 3);
+    let index = (exponent / 3) as usize;
+    let suffix = match LETTERS_SUFFIXES.get(index) {
+        Some(suffix) => suffix.to_string(),
+        None => {
+            let letters = index - LETTERS_SUFFIXES.len();
+            if letters >= 26 * 26 {
+                return format_exponent(decimal, unit, 1);
+            }
+            let letter = |offset: usize| char::from(b'a' + offset as u8);
+            format!("{}{}", letter(letters / 26), letter(letters % 26))
+        }
+    };
+    format!("{:.2}{} {}", mantissa, suffix, unit)
+}
+
+fn format_decimal(decimal: Decimal, unit: &str, notation: Notation) -> String {
+    let log10 = decimal.abs_log10();
+    if log10.is_nan() {
+        return format!("NaN {}", unit);
+    }
+    if log10 < 3.0 {
+        return format!("{} {}", decimal.to_fixed(2), unit);
+    }
+    match notation {
+        Notation::LinuxKernels | Notation::Si | Notation::Codebases => format_si(decimal, unit),
+        Notation::Scientific => format_exponent(decimal, unit, 1),
+        Notation::Engineering => format_exponent(decimal, unit, 3),
+        Notation::Letters => format_letters(decimal, unit),
+    }
+}
+
+pub(crate) fn format_decimal_devs(decimal: Decimal, notation: Notation) -> String {
+    format_decimal(decimal, "devs", notation)
 }
 
-pub(crate) fn format_decimal_bugs(decimal: Decimal) -> String {
-    format_decimal(decimal, "bugs")
+pub(crate) fn format_decimal_bugs(decimal: Decimal, notation: Notation) -> String {
+    format_decimal(decimal, "bugs", notation)
 }
 
-pub(crate) fn format_decimal_hrs(decimal: Decimal) -> String {
-    format_decimal(decimal, "hrs")
+pub(crate) fn format_decimal_hrs(decimal: Decimal, notation: Notation) -> String {
+    format_decimal(decimal, "hrs", notation)
 }
 
-pub(crate) fn format_decimal_pms(decimal: Decimal) -> String {
-    format_decimal(decimal, "pms")
+pub(crate) fn format_decimal_pms(decimal: Decimal, notation: Notation) -> String {
+    format_decimal(decimal, "pms", notation)
 }
 
-pub(crate) fn format_decimal_features(decimal: Decimal) -> String {
-    format_decimal(decimal, "features")
+pub(crate) fn format_decimal_features(decimal: Decimal, notation: Notation) -> String {
+    format_decimal(decimal, "features", notation)
 }
 
-pub(crate) fn format_decimal_loc(decimal: Decimal) -> String {
+fn format_loc_in_codebases(decimal: Decimal) -> String {
+    let Some(codebase) = current_codebase(decimal) else {
+        return format_decimal(decimal, "loc", Notation::Si);
+    };
+    let ratio = decimal / Decimal::new(codebase.loc);
+    // past the biggest codebase the ratio keeps growing
+    let ratio = if ratio.abs_log10() < 3.0 {
+        ratio.to_fixed(2)
+    } else {
+        let (mantissa, exponent) = split_exponent(ratio, 1);
+        format!("{:.2}e{}", mantissa, exponent)
+    };
+    format!("{} {}", ratio, codebase.plural)
+}
+
+pub(crate) fn format_decimal_loc(decimal: Decimal, notation: Notation) -> String {
+    match notation {
+        Notation::LinuxKernels => {}
+        Notation::Codebases => return format_loc_in_codebases(decimal),
+        _ => return format_decimal(decimal, "loc", notation),
+    }
     // Linux Kernel 5.11 approximately has 30 millions lines of code cf https://en.wikipedia.org/wiki/Linux_kernel
     let linux_kernel_loc: Decimal = Decimal::new(30e6);
     let stages: Vec<(Decimal, Decimal, String)> = vec![
@@ -97,3 +208,119 @@ pub(crate) fn format_decimal_loc(decimal: Decimal) -> String {
     }
     format!("{} LK", (decimal / linux_kernel_loc).to_precision(2))
 }
+
+#[component]
+pub(crate) fn NotationSelect() -> Element {
+    let mut state = use_game_state();
+    let current = state.read().settings.notation;
+    rsx! {
+        label {
+            "notation "
+            select {
+                onchange: move |event: Event<FormData>| {
+                    if let Some(notation) = Notation::ALL
+                        .into_iter()
+                        .find(|notation| notation.name() == event.value())
+                    {
+                        state.write().settings.notation = notation;
+                    }
+                },
+                for notation in Notation::ALL {
+                    option {
+                        value: notation.name(),
+                        selected: notation == current,
+                        "{notation.name()}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bugs(value: f64, notation: Notation) -> String {
+        format_decimal_bugs(Decimal::new(value), notation)
+    }
+
+    #[test]
+    fn small_values_are_the_same_in_every_notation() {
+        for notation in Notation::ALL {
+            assert_eq!(bugs(0.0, notation), "0.00 bugs");
+            assert_eq!(bugs(999.0, notation), "999.00 bugs");
+            assert_eq!(bugs(-12.5, notation), "-12.50 bugs");
+        }
+    }
+
+    #[test]
+    fn si_prefixes() {
+        assert_eq!(bugs(1e3, Notation::Si), "1.00 Kilobugs");
+        assert_eq!(bugs(999_999.0, Notation::Si), "1.00 Megabugs");
+        assert_eq!(bugs(1.5e21, Notation::Si), "1.50 Zettabugs");
+        assert_eq!(bugs(1e24, Notation::Si), "1.00 Yottabugs");
+        assert_eq!(bugs(1e27, Notation::Si), "1.00 Ronnabugs");
+        assert_eq!(bugs(999e30, Notation::Si), "999.00 Quettabugs");
+        assert_eq!(bugs(1e33, Notation::Si), "1.00e33 bugs");
+        assert_eq!(bugs(-2.5e6, Notation::Si), "-2.50 Megabugs");
+    }
+
+    #[test]
+    fn scientific_and_engineering() {
+        assert_eq!(bugs(1e3, Notation::Scientific), "1.00e3 bugs");
+        assert_eq!(bugs(12_345.0, Notation::Scientific), "1.23e4 bugs");
+        assert_eq!(bugs(9.999e5, Notation::Scientific), "1.00e6 bugs");
+        assert_eq!(bugs(-4.2e40, Notation::Scientific), "-4.20e40 bugs");
+        assert_eq!(bugs(12_340.0, Notation::Engineering), "12.34e3 bugs");
+        assert_eq!(bugs(1.5e44, Notation::Engineering), "150.00e42 bugs");
+        assert_eq!(bugs(-1e6, Notation::Engineering), "-1.00e6 bugs");
+        let huge = Decimal::new(1.5) * Decimal::pow10(400.0);
+        assert_eq!(
+            format_decimal_bugs(huge, Notation::Scientific),
+            "1.50e400 bugs"
+        );
+    }
+
+    #[test]
+    fn letters() {
+        assert_eq!(bugs(1e3, Notation::Letters), "1.00K bugs");
+        assert_eq!(bugs(2.5e12, Notation::Letters), "2.50T bugs");
+        assert_eq!(bugs(1e15, Notation::Letters), "1.00aa bugs");
+        assert_eq!(bugs(1e18, Notation::Letters), "1.00ab bugs");
+        assert_eq!(bugs(1e93, Notation::Letters), "1.00ba bugs");
+        assert_eq!(bugs(-3e15, Notation::Letters), "-3.00aa bugs");
+        let beyond_zz = Decimal::pow10(3.0 * (5.0 + 26.0 * 26.0));
+        assert_eq!(
+            format_decimal_bugs(beyond_zz, Notation::Letters),
+            "1.00e2043 bugs"
+        );
+    }
+
+    #[test]
+    fn loc_in_linux_kernels() {
+        let loc = |value: f64| format_decimal_loc(Decimal::new(value), Notation::LinuxKernels);
+        assert_eq!(loc(999.0), "999.00 loc");
+        assert_eq!(loc(1e3), "1.00 Kiloloc");
+        assert_eq!(loc(30e6), "1.00 Linux Kernels (LK)");
+        assert_eq!(loc(-30e9), "-1.00 KiloLK");
+        assert_eq!(
+            format_decimal_loc(Decimal::new(30e6), Notation::Si),
+            "30.00 Megaloc"
+        );
+    }
+
+    #[test]
+    fn loc_in_codebases() {
+        let loc = |value: f64| format_decimal_loc(Decimal::new(value), Notation::Codebases);
+        assert_eq!(loc(144e3), "144.00 Kiloloc");
+        assert_eq!(loc(145e3), "1.00 Apollo 11s");
+        assert_eq!(loc(52.5e6), "1.50 Chromiums");
+        assert_eq!(loc(-4e9), "-2.00 Google monorepos");
+        assert_eq!(loc(5e15), "5.00e3 GitHubs");
+        assert_eq!(
+            format_decimal_bugs(Decimal::new(1e3), Notation::Codebases),
+            "1.00 Kilobugs"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+/**
+ * Compact duration, e.g. "1h02m03s", "3m12s" or "42s"
+ */
+pub(crate) fn format_duration(seconds: f64) -> String {
+    let total_seconds = seconds.max(0.0).ceil() as u64;
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds / 60) % 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{}h{:02}m{:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m{:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
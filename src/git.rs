@@ -0,0 +1,70 @@
+#![allow(non_snake_case)]
+use crate::actions::Action;
+use crate::confirmation::request_action;
+use crate::constants::{use_game_constants, Research};
+use crate::format_decimal::{format_decimal_bugs, format_decimal_features, format_decimal_loc};
+use crate::format_duration::format_duration;
+use crate::snapshot::{Snapshot, StateField};
+use crate::state::use_game_state;
+use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// committing past this drops the oldest checkpoint
+pub(crate) const MAX_COMMITS: usize = 3;
+
+/**
+ * Checkpoint of the codebase, which rm -rf leaves alone and git revert restores
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Commit {
+    pub game_time: f64,
+    pub snapshot: Snapshot,
+    // `State::loc_spent` when committing
+    #[serde(default)]
+    pub loc_spent: Decimal,
+}
+
+#[component]
+pub(crate) fn Commits() -> Element {
+    let mut state = use_game_state();
+    let constants = use_game_constants();
+    let commits = state.read().commits.clone();
+    let notation = state.read().settings.notation;
+    let can_revert = state.read().researched.contains(&Research::GitRevert);
+    let loss_percent = constants.git_revert_loc_loss_ratio * Decimal::new(100.0);
+    rsx! {
+        if !commits.is_empty() {
+            div {
+                class: "commits",
+                p {"git log"}
+                for (index, commit) in commits.into_iter().enumerate() {
+                    div {
+                        class: "commit",
+                        span {
+                            "#{index + 1} at {format_duration(commit.game_time)}: "
+                            "{format_decimal_loc(commit.snapshot.get(StateField::Loc).unwrap_or_default(), notation)}, "
+                            "{format_decimal_bugs(commit.snapshot.get(StateField::Bugs).unwrap_or_default(), notation)}, "
+                            "{format_decimal_features(commit.snapshot.get(StateField::Features).unwrap_or_default(), notation)}"
+                        }
+                        if can_revert {
+                            button {
+                                class: "repeatable-action-button",
+                                title: "restores this checkpoint, minus the loc spent since, losing {loss_percent}% of its loc",
+                                onclick: {
+                                    let constants = constants.clone();
+                                    move |_| {
+                                        request_action(&mut state.write(), &constants, Action::GitRevert(index));
+                                    }
+                                },
+                                "git revert"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
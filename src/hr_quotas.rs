@@ -0,0 +1,166 @@
+#![allow(non_snake_case)]
+//! Hiring quotas: how HR split their hiring between roles, chosen by the player once researched.
+
+use crate::constants::{GameConstants, Research};
+use crate::producers::Producer;
+use crate::state::{use_game_state, State};
+use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/**
+ * Positions of the quota sliders, each quota is its slider share of the total
+ */
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct HrQuotas {
+    // between 0 and 1, indexed as `Producer::ALL`
+    pub weights: [Decimal; 5],
+}
+
+impl HrQuotas {
+    // sliders start at the fixed quotas
+    pub(crate) fn new(constants: &GameConstants) -> Self {
+        HrQuotas {
+            weights: [
+                constants.hrs_interns_quota,
+                constants.hrs_junior_devs_quota,
+                constants.hrs_senior_devs_quota,
+                constants.hrs_hrs_quota,
+                constants.hrs_pms_quota,
+            ],
+        }
+    }
+
+    pub(crate) fn weight(&self, producer: Producer) -> Decimal {
+        self.weights[producer as usize]
+    }
+
+    pub(crate) fn set(&mut self, producer: Producer, weight: Decimal) {
+        self.weights[producer as usize] = weight.max(&Decimal::ZERO).min(&Decimal::ONE);
+    }
+
+    /**
+     * Quotas summing to 1 over the roles HR can hire, all zero when their sliders are
+     */
+    pub(crate) fn normalized(&self, state: &State) -> [Decimal; 5] {
+        let weights = Producer::ALL.map(|producer| {
+            if is_target(producer, state) {
+                self.weight(producer)
+            } else {
+                Decimal::ZERO
+            }
+        });
+        let total = weights
+            .iter()
+            .fold(Decimal::ZERO, |sum, weight| sum + *weight);
+        if total == Decimal::ZERO {
+            return [Decimal::ZERO; 5];
+        }
+        weights.map(|weight| weight / total)
+    }
+}
+
+/// roles HR can hire, themselves only with recursive HR
+fn is_target(producer: Producer, state: &State) -> bool {
+    match producer {
+        Producer::Hrs => state.researched.contains(&Research::RecursiveHR),
+        _ => state.researched.contains(&producer.require()),
+    }
+}
+
+/**
+ * Replaces the fixed quotas with the player ones, once hiring quotas are researched
+ */
+pub(crate) fn apply_hr_quotas(state: &mut State) {
+    if !state.researched.contains(&Research::HiringQuotas) {
+        return;
+    }
+    let [interns, junior_devs, senior_devs, hrs, pms] = state.hr_quotas.normalized(state);
+    state.hrs_interns_quota = interns;
+    state.hrs_junior_devs_quota = junior_devs;
+    state.hrs_senior_devs_quota = senior_devs;
+    state.hrs_hrs_quota = hrs;
+    state.hrs_pms_quota = pms;
+}
+
+#[component]
+pub(crate) fn HrQuotaSliders() -> Element {
+    let mut state = use_game_state();
+    if !state.read().researched.contains(&Research::HiringQuotas) {
+        return None;
+    }
+    let quotas = state.read().hr_quotas.normalized(&state.read());
+    let sliders: Vec<(Producer, f64, f64)> = Producer::ALL
+        .into_iter()
+        .filter(|producer| is_target(*producer, &state.read()))
+        .map(|producer| {
+            (
+                producer,
+                (state.read().hr_quotas.weight(producer).to_number() * 100.0).round(),
+                quotas[producer as usize].to_number() * 100.0,
+            )
+        })
+        .collect();
+    rsx! {
+        table {
+            class: "hr-quotas",
+            for (producer, position, percent) in sliders {
+                tr {
+                    td {"{producer.name()}"}
+                    td {
+                        input {
+                            r#type: "range",
+                            min: 0,
+                            max: 100,
+                            value: "{position}",
+                            oninput: move |event: Event<FormData>| {
+                                if let Ok(position) = event.value().parse::<f64>() {
+                                    state.write().hr_quotas.set(producer, Decimal::new(position / 100.0));
+                                }
+                            },
+                        }
+                    }
+                    td {"{percent:.1}%"}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotas_are_normalized_over_hireable_roles() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.hrs = Decimal::new(100.0);
+        state
+            .researched
+            .extend([Research::HumanResources, Research::HiringQuotas]);
+        for producer in Producer::ALL {
+            state.hr_quotas.set(producer, Decimal::new(0.5));
+        }
+        // no role can be hired yet
+        assert_eq!(state.hr_quotas.normalized(&state), [Decimal::ZERO; 5]);
+        state.researched.extend([
+            Research::Internship,
+            Research::JuniorDevsPosition,
+            Research::SeniorDevsPosition,
+            Research::ProjectManagement,
+        ]);
+        let quarter = Decimal::new(0.25);
+        assert_eq!(
+            state.hr_quotas.normalized(&state),
+            [quarter, quarter, quarter, Decimal::ZERO, quarter]
+        );
+
+        state.update(Decimal::new(10.0));
+        assert_eq!(state.hrs_interns_quota, quarter);
+        assert_eq!(state.hrs_hrs_quota, Decimal::ZERO);
+        assert!(state.pms > Decimal::ZERO);
+    }
+}
@@ -0,0 +1,216 @@
+//! Invariants of `State::update` and of every action, over randomized states.
+//! Values span the whole `Decimal` range so that late game magnitudes are covered too.
+
+use crate::actions::Action;
+use crate::constants::{GameConstants, Research};
+use crate::hr_quotas::apply_hr_quotas;
+use crate::producers::Producer;
+use crate::quest_data::quest_definitions;
+use crate::random_events::RandomEvent;
+use crate::rates::Rates;
+use crate::research_data::research_definitions;
+use crate::snapshot::StateField;
+use crate::state::State;
+use break_infinity::{from_mantissa_exponent, Decimal};
+use proptest::prelude::*;
+
+// enough to catch rounding drift, far below any gameplay effect
+const RELATIVE_TOLERANCE: f64 = 1e-9;
+
+fn decimal() -> impl Strategy<Value = Decimal> {
+    prop_oneof![
+        Just(Decimal::ZERO),
+        (0.0..1e6f64).prop_map(Decimal::new),
+        (1.0..10.0f64, -300.0..3000.0f64)
+            .prop_map(|(mantissa, exponent)| from_mantissa_exponent(mantissa, exponent.floor())),
+    ]
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    let mut actions = vec![
+        Action::Code,
+        Action::Debug,
+        Action::Rmrf,
+        Action::SmartStaffing,
+        Action::GitCommit,
+        Action::GitRevert(0),
+    ];
+    for producer in Producer::ALL {
+        actions.push(Action::Hire(producer));
+        actions.push(Action::HireMax(producer));
+    }
+    proptest::sample::select(actions)
+}
+
+fn all_researches() -> Vec<Research> {
+    let constants = GameConstants::default();
+    research_definitions(&constants)
+        .into_iter()
+        .chain(quest_definitions(&constants))
+        .map(|definition| definition.research)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+enum Step {
+    Update,
+    Perform(Action),
+    // an event fired and the player picked a choice, if affordable
+    Event(usize, usize),
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        3 => Just(Step::Update),
+        2 => action().prop_map(Step::Perform),
+        1 => (0..RandomEvent::ALL.len(), 0..2usize).prop_map(|(event, choice)| Step::Event(event, choice)),
+    ]
+}
+
+/**
+ * What a randomized `State` is built from, `State` itself cannot be printed on failure
+ */
+#[derive(Debug, Clone)]
+struct StateSeed {
+    values: Vec<Decimal>,
+    researched: Vec<Research>,
+    dt: f64,
+}
+
+impl StateSeed {
+    fn build(&self) -> State {
+        let mut state = State::new(GameConstants::default());
+        for (field, value) in StateField::ALL.into_iter().zip(self.values.iter()) {
+            *field.get_mut(&mut state) = *value;
+        }
+        state.researched.extend(self.researched.iter().cloned());
+        state.dt = Decimal::new(self.dt);
+        state
+    }
+}
+
+prop_compose! {
+    fn state_seed()(
+        values in proptest::collection::vec(decimal(), StateField::ALL.len()),
+        researched in proptest::sample::subsequence(all_researches(), 0..=all_researches().len()),
+        dt in prop_oneof![1e-4..1.0f64, 1.0..1e3f64],
+    ) -> StateSeed {
+        StateSeed { values, researched, dt }
+    }
+}
+
+fn devs(state: &State) -> Decimal {
+    state.interns
+        + state.manual_interns
+        + state.junior_devs
+        + state.manual_junior_devs
+        + state.senior_devs
+        + state.manual_senior_devs
+}
+
+fn assert_close(actual: Decimal, expected: Decimal, what: &str) {
+    let scale = actual.abs().max(&expected.abs());
+    let difference = (actual - expected).abs();
+    assert!(
+        difference <= scale * Decimal::new(RELATIVE_TOLERANCE),
+        "{}: {} != {}",
+        what,
+        actual,
+        expected
+    );
+}
+
+fn assert_non_negative(state: &State, after: &str) {
+    for field in StateField::ALL {
+        let value = field.get(state);
+        // also catches NaN, which compares false with everything
+        assert!(
+            value >= Decimal::ZERO,
+            "{} is {} after {}",
+            field.name(),
+            value,
+            after
+        );
+    }
+}
+
+/**
+ * Checks every invariant over a single tick
+ */
+fn checked_update(state: &mut State) {
+    // as `State::update` does before computing its rates
+    apply_hr_quotas(state);
+    let rates = Rates::new(state);
+    assert!(
+        rates.bugs_converted <= state.bugs,
+        "{} bugs converted out of {}",
+        rates.bugs_converted,
+        state.bugs
+    );
+    let retired_devs = state.retired_devs;
+    let staff = devs(state) + state.retired_devs + state.pms;
+    let hired =
+        rates.hired_interns + rates.hired_junior_devs + rates.hired_senior_devs + rates.hired_pms;
+
+    state.update(Decimal::new(10.0));
+
+    assert!(
+        state.retired_devs >= retired_devs,
+        "retired devs decreased: {} < {}",
+        state.retired_devs,
+        retired_devs
+    );
+    // devs only move between tiers, to retirement or to management, apart from new hires
+    assert_close(
+        devs(state) + state.retired_devs + state.pms,
+        staff + hired,
+        "devs conservation",
+    );
+    assert_non_negative(state, "update");
+}
+
+proptest! {
+    #[test]
+    fn update_keeps_invariants(seed in state_seed(), ticks in 1..50usize) {
+        let mut state = seed.build();
+        for _ in 0..ticks {
+            checked_update(&mut state);
+        }
+    }
+
+    #[test]
+    fn legal_actions_keep_invariants(seed in state_seed(), steps in proptest::collection::vec(step(), 1..50)) {
+        let constants = GameConstants::default();
+        let mut state = seed.build();
+        for step in steps {
+            match step {
+                Step::Update => checked_update(&mut state),
+                Step::Perform(action) => {
+                    let retired_devs = state.retired_devs;
+                    action.apply(&mut state, &constants);
+                    prop_assert_eq!(state.retired_devs, retired_devs);
+                    assert_non_negative(&state, &action.name());
+                }
+                Step::Event(event, choice) => {
+                    let event = RandomEvent::ALL[event];
+                    // unaffordable choices are refused
+                    if event.resolve(choice, &mut state, &constants) {
+                        assert_non_negative(&state, event.title());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn failed_actions_leave_state_untouched(seed in state_seed(), action in action()) {
+        let constants = GameConstants::default();
+        let before = seed.build();
+        let mut after = seed.build();
+        if !action.apply(&mut after, &constants) {
+            for field in StateField::ALL {
+                prop_assert_eq!(field.get(&after), field.get(&before), "{}", field.name());
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+#![allow(non_snake_case)]
+use crate::actions::Action;
+use crate::confirmation::request_action;
+use crate::constants::GameConstants;
+use crate::producers::Producer;
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use dioxus::prelude::{Signal, Writable};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use web_time::Instant;
+
+// holding a key repeats its action at most 10 times per second, same as a fast clicker
+const MIN_REPEAT_INTERVAL: Duration = Duration::from_millis(100);
+
+/**
+ * Key bound to each action, holding shift turns a hire into a max hire
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Keybindings {
+    bindings: Vec<(Action, String)>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        let mut bindings = vec![
+            (Action::Code, "c".to_string()),
+            (Action::Debug, "d".to_string()),
+        ];
+        for (index, producer) in Producer::ALL.into_iter().enumerate() {
+            bindings.push((Action::Hire(producer), (index + 1).to_string()));
+        }
+        Keybindings { bindings }
+    }
+}
+
+impl Keybindings {
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &(Action, String)> {
+        self.bindings.iter()
+    }
+
+    pub(crate) fn action(&self, key: &str, shift: bool) -> Option<Action> {
+        let (action, _) = self.bindings.iter().find(|(_, bound)| bound == key)?;
+        match (action, shift) {
+            (Action::Hire(producer), true) => Some(Action::HireMax(*producer)),
+            (action, _) => Some(*action),
+        }
+    }
+
+    /**
+     * A key triggers a single action: binding it unbinds it from any other action
+     */
+    pub(crate) fn rebind(&mut self, action: Action, key: &str) {
+        for (bound_action, bound) in self.bindings.iter_mut() {
+            if *bound_action == action {
+                *bound = key.to_string();
+            } else if bound == key {
+                bound.clear();
+            }
+        }
+    }
+}
+
+/**
+ * Performs bound actions on key presses, for the whole page lifetime
+ */
+pub(crate) async fn listen_keys(mut state: Signal<State>, constants: GameConstants) {
+    let mut keys = eval(
+        r#"
+        document.addEventListener('keydown', (event) => {
+            // leave browser shortcuts and form inputs alone
+            if (event.ctrlKey || event.metaKey || event.altKey) return;
+            if (['INPUT', 'SELECT', 'TEXTAREA'].includes(event.target.tagName)) return;
+            // digits by position so that shift + digit works on every layout
+            const key = event.code.startsWith('Digit') ? event.code.slice(5) : event.key.toLowerCase();
+            dioxus.send([key, event.shiftKey]);
+        });
+        "#,
+    );
+    let mut last_performed: HashMap<Action, Instant> = HashMap::new();
+    while let Ok(value) = keys.recv().await {
+        let Ok((key, shift)) = serde_json::from_value::<(String, bool)>(value) else {
+            continue;
+        };
+        let Some(action) = state.read().settings.keybindings.action(&key, shift) else {
+            continue;
+        };
+        let now = Instant::now();
+        if last_performed
+            .get(&action)
+            .is_some_and(|last| now.duration_since(*last) < MIN_REPEAT_INTERVAL)
+        {
+            continue;
+        }
+        last_performed.insert(action, now);
+        if request_action(&mut state.write(), &constants, action) {
+            state.write().logs.log(
+                LogCategory::Action,
+                &format!("pressed {}: {}", key, action.name()),
+            );
+            if state.read().speedrun_start.is_none() {
+                state.write().speedrun_start = Some(Instant::now());
+            }
+        }
+    }
+}
+
+#[component]
+pub(crate) fn KeybindingsPanel() -> Element {
+    let mut state = use_game_state();
+    let bindings: Vec<(Action, String)> =
+        state.read().settings.keybindings.iter().cloned().collect();
+    rsx! {
+        details {
+            summary {"keybindings (shift to hire max)"}
+            table {
+                for (action, key) in bindings {
+                    tr {
+                        td {"{action.name()}"}
+                        td {
+                            input {
+                                r#type: "text",
+                                maxlength: 1,
+                                size: 2,
+                                value: "{key}",
+                                onchange: move |event: Event<FormData>| {
+                                    let key = event.value().to_lowercase();
+                                    state.write().settings.keybindings.rebind(action, &key);
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+            button {
+                onclick: move |_| {
+                    state.write().settings.keybindings = Keybindings::default();
+                },
+                "reset keybindings"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_turns_hires_into_max_hires() {
+        let keybindings = Keybindings::default();
+        assert_eq!(keybindings.action("c", true), Some(Action::Code));
+        assert_eq!(
+            keybindings.action("1", false),
+            Some(Action::Hire(Producer::Interns))
+        );
+        assert_eq!(
+            keybindings.action("5", true),
+            Some(Action::HireMax(Producer::Pms))
+        );
+        assert_eq!(keybindings.action("x", false), None);
+    }
+
+    #[test]
+    fn rebinding_a_key_unbinds_it_elsewhere() {
+        let mut keybindings = Keybindings::default();
+        keybindings.rebind(Action::Debug, "c");
+        assert_eq!(keybindings.action("c", false), Some(Action::Debug));
+        assert_eq!(keybindings.action("d", false), None);
+    }
+}
@@ -0,0 +1,390 @@
+#![allow(non_snake_case)]
+//! Smart staffing: who gets laid off, the severance it costs and the bugs it causes.
+
+use crate::constants::{use_game_constants, GameConstants, Research};
+use crate::format_decimal::{format_decimal_devs, format_decimal_loc};
+use crate::modifiers::{Modifier, ModifierSource, Operation, Stat};
+use crate::producers::Producer;
+use crate::rates::Rates;
+use crate::state::{use_game_state, State};
+use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub(crate) enum LayoffPolicy {
+    // the same share of every tier
+    #[default]
+    Uniform,
+    // a share of a single tier
+    Tier(Producer),
+    // the share of all devs, from interns and junior devs only
+    KeepSeniors,
+    // the share of all devs, by tier regardless of when each dev was hired:
+    // interns, then junior devs, then senior devs
+    #[serde(alias = "LastInFirstOut")]
+    JuniorsFirst,
+    // down to a total dev headcount, the same share of every tier
+    TargetHeadcount(Decimal),
+}
+
+impl LayoffPolicy {
+    // every policy, with the current headcount as target
+    fn choices(state: &State) -> Vec<LayoffPolicy> {
+        let mut choices = vec![LayoffPolicy::Uniform];
+        choices.extend(Producer::DEVS.map(LayoffPolicy::Tier));
+        choices.extend([
+            LayoffPolicy::KeepSeniors,
+            LayoffPolicy::JuniorsFirst,
+            LayoffPolicy::TargetHeadcount(devs(state).round()),
+        ]);
+        choices
+    }
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            LayoffPolicy::Uniform => "every tier".to_string(),
+            LayoffPolicy::Tier(producer) => format!("{} only", producer.name()),
+            LayoffPolicy::KeepSeniors => "keep senior devs".to_string(),
+            LayoffPolicy::JuniorsFirst => "juniors first".to_string(),
+            LayoffPolicy::TargetHeadcount(_) => "target headcount".to_string(),
+        }
+    }
+}
+
+fn headcount(producer: Producer, state: &State) -> Decimal {
+    producer.auto(state) + producer.manual(state)
+}
+
+fn devs(state: &State) -> Decimal {
+    Producer::DEVS.iter().fold(Decimal::ZERO, |sum, producer| {
+        sum + headcount(*producer, state)
+    })
+}
+
+/// `count` devs taken from `headcounts` in order, each tier emptied before the next one
+fn in_order(headcounts: [Decimal; 3], mut count: Decimal) -> [Decimal; 3] {
+    headcounts.map(|headcount| {
+        let taken = headcount.min(&count);
+        count -= taken;
+        taken
+    })
+}
+
+/**
+ * What smart staffing does with the current policy, computed before doing it for the preview
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LayoffPlan {
+    // devs laid off in each tier of `Producer::DEVS`
+    pub laid_off: [Decimal; 3],
+    pub severance: Decimal,
+    // multiplier of the devs bugs ratios while morale recovers
+    pub bugs_multiplier: Decimal,
+}
+
+impl LayoffPlan {
+    pub(crate) fn new(state: &State, constants: &GameConstants) -> Self {
+        let headcounts = Producer::DEVS.map(|producer| headcount(producer, state));
+        let total = devs(state);
+        let share = constants.smart_staffing_layoff_ratio;
+        let laid_off = match state.layoff_policy {
+            LayoffPolicy::Uniform => headcounts.map(|headcount| headcount * share),
+            LayoffPolicy::Tier(tier) => {
+                let mut laid_off = [Decimal::ZERO; 3];
+                for (index, producer) in Producer::DEVS.into_iter().enumerate() {
+                    if producer == tier {
+                        laid_off[index] = headcounts[index] * share;
+                    }
+                }
+                laid_off
+            }
+            LayoffPolicy::KeepSeniors => {
+                let juniors = headcounts[0] + headcounts[1];
+                let ratio = if juniors > Decimal::ZERO {
+                    (total * share / juniors).min(&Decimal::ONE)
+                } else {
+                    Decimal::ZERO
+                };
+                [headcounts[0] * ratio, headcounts[1] * ratio, Decimal::ZERO]
+            }
+            LayoffPolicy::JuniorsFirst => in_order(headcounts, total * share),
+            LayoffPolicy::TargetHeadcount(target) => {
+                let ratio = if total > target {
+                    (total - target) / total
+                } else {
+                    Decimal::ZERO
+                };
+                headcounts.map(|headcount| headcount * ratio)
+            }
+        };
+        let severance =
+            Producer::DEVS
+                .iter()
+                .zip(laid_off)
+                .fold(Decimal::ZERO, |sum, (producer, laid_off)| {
+                    sum + laid_off
+                        * producer.loc_base_cost(constants)
+                        * constants.layoff_severance_ratio
+                });
+        let laid_off_total = laid_off
+            .iter()
+            .fold(Decimal::ZERO, |sum, laid_off| sum + *laid_off);
+        let laid_off_share = if total > Decimal::ZERO {
+            laid_off_total / total
+        } else {
+            Decimal::ZERO
+        };
+        LayoffPlan {
+            laid_off,
+            severance,
+            bugs_multiplier: Decimal::ONE + constants.layoff_morale_bugs_ratio * laid_off_share,
+        }
+    }
+
+    /**
+     * Lays off auto and manual hires alike, affordability is checked by `Action::is_available`
+     */
+    pub(crate) fn apply(&self, state: &mut State, constants: &GameConstants) {
+        state.spend(self.severance);
+        for (producer, laid_off) in Producer::DEVS.into_iter().zip(self.laid_off) {
+            let headcount = headcount(producer, state);
+            if headcount > Decimal::ZERO {
+                let kept = Decimal::ONE - laid_off / headcount;
+                *producer.auto_mut(state) *= kept;
+                *producer.manual_mut(state) *= kept;
+            }
+        }
+        if self.bugs_multiplier > Decimal::ONE {
+            let expires_at = state.game_time + constants.layoff_morale_duration_seconds.to_number();
+            for target in [
+                Stat::InternsBugsRatio,
+                Stat::JuniorDevsBugsRatio,
+                Stat::SeniorDevsBugsRatio,
+            ] {
+                state.modifiers.push(Modifier {
+                    expires_at: Some(expires_at),
+                    ..Modifier::new(
+                        "low morale",
+                        target,
+                        Operation::Multiply,
+                        self.bugs_multiplier,
+                        ModifierSource::Layoffs,
+                    )
+                });
+            }
+        }
+    }
+
+    /// devs left once the plan is applied
+    fn devs_after(&self, state: &State) -> Decimal {
+        self.laid_off
+            .iter()
+            .fold(devs(state), |sum, laid_off| sum - *laid_off)
+    }
+
+    /// loc per tick once the plan is applied: layoffs change headcounts, not what each dev writes
+    fn loc_after(&self, rates: &Rates) -> Decimal {
+        [&rates.interns, &rates.junior_devs, &rates.senior_devs]
+            .into_iter()
+            .zip(self.laid_off)
+            .fold(Decimal::ZERO, |sum, (tier, laid_off)| {
+                sum + (tier.auto + tier.manual - laid_off) * tier.loc_per_dev
+            })
+    }
+}
+
+/**
+ * Headcount and production before and after smart staffing, shown before confirming it
+ */
+#[component]
+pub(crate) fn LayoffPreview() -> Element {
+    let state = use_game_state();
+    let constants = use_game_constants();
+    let state = state.read();
+    let notation = state.settings.notation;
+    let plan = LayoffPlan::new(&state, &constants);
+    let rates = Rates::new(&state);
+    let affordable = state.loc >= plan.severance;
+    let bugs_percent = ((plan.bugs_multiplier - Decimal::ONE) * Decimal::new(100.0)).round();
+    let morale_seconds = constants.layoff_morale_duration_seconds;
+    rsx! {
+        table {
+            class: "layoff-preview",
+            tr {
+                td {"headcount"}
+                td {"{format_decimal_devs(devs(&state), notation)}"}
+                td {"→ {format_decimal_devs(plan.devs_after(&state), notation)}"}
+            }
+            tr {
+                td {"LOC/s"}
+                td {"{format_decimal_loc(rates.loc * state.dt_seconds, notation)}"}
+                td {"→ {format_decimal_loc(plan.loc_after(&rates) * state.dt_seconds, notation)}"}
+            }
+            tr {
+                td {"severance"}
+                td {
+                    colspan: 2,
+                    class: if affordable { "" } else { "unaffordable" },
+                    "{format_decimal_loc(plan.severance, notation)}"
+                }
+            }
+            tr {
+                td {"morale"}
+                td {
+                    colspan: 2,
+                    "+{bugs_percent}% bugs for {morale_seconds}s"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub(crate) fn LayoffPolicyPanel() -> Element {
+    let mut state = use_game_state();
+    if !state.read().researched.contains(&Research::SmartStaffing) {
+        return None;
+    }
+    let policy = state.read().layoff_policy;
+    let choices = LayoffPolicy::choices(&state.read());
+    let notation = state.read().settings.notation;
+    rsx! {
+        div {
+            class: "layoff-policy",
+            select {
+                onchange: move |event: Event<FormData>| {
+                    let choices = LayoffPolicy::choices(&state.read());
+                    if let Some(choice) = choices.into_iter().find(|choice| choice.name() == event.value()) {
+                        state.write().layoff_policy = choice;
+                    }
+                },
+                for choice in choices {
+                    option {
+                        value: choice.name(),
+                        selected: choice.name() == policy.name(),
+                        "{choice.name()}"
+                    }
+                }
+            }
+            if let LayoffPolicy::TargetHeadcount(target) = policy {
+                input {
+                    r#type: "number",
+                    min: 0,
+                    title: "{format_decimal_devs(target, notation)}",
+                    value: "{target.to_number()}",
+                    onchange: move |event: Event<FormData>| {
+                        if let Ok(target) = event.value().parse::<f64>() {
+                            state.write().layoff_policy =
+                                LayoffPolicy::TargetHeadcount(Decimal::new(target.max(0.0)));
+                        }
+                    },
+                }
+            }
+            LayoffPreview {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::Action;
+
+    fn staffed(policy: LayoffPolicy) -> (State, GameConstants) {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.interns = Decimal::new(50.0);
+        state.manual_interns = Decimal::new(50.0);
+        state.junior_devs = Decimal::new(60.0);
+        state.senior_devs = Decimal::new(40.0);
+        state.layoff_policy = policy;
+        (state, constants)
+    }
+
+    #[test]
+    fn policies_pick_who_is_laid_off() {
+        let laid_off = |policy| {
+            let (state, constants) = staffed(policy);
+            LayoffPlan::new(&state, &constants)
+                .laid_off
+                .map(|laid_off| laid_off.to_number().round())
+        };
+        assert_eq!(laid_off(LayoffPolicy::Uniform), [20.0, 12.0, 8.0]);
+        assert_eq!(
+            laid_off(LayoffPolicy::Tier(Producer::SeniorDevs)),
+            [0.0, 0.0, 8.0]
+        );
+        // 20% of 200 devs, spread over the 160 interns and junior devs
+        assert_eq!(laid_off(LayoffPolicy::KeepSeniors), [25.0, 15.0, 0.0]);
+        assert_eq!(laid_off(LayoffPolicy::JuniorsFirst), [40.0, 0.0, 0.0]);
+        // saved before the policy was named after what it does
+        assert_eq!(
+            serde_json::from_str::<LayoffPolicy>("\"LastInFirstOut\"").unwrap(),
+            LayoffPolicy::JuniorsFirst
+        );
+        assert_eq!(
+            laid_off(LayoffPolicy::TargetHeadcount(Decimal::new(150.0))),
+            [25.0, 15.0, 10.0]
+        );
+        assert_eq!(
+            laid_off(LayoffPolicy::TargetHeadcount(Decimal::new(500.0))),
+            [0.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn layoffs_cost_severance_and_morale() {
+        let (mut state, constants) = staffed(LayoffPolicy::JuniorsFirst);
+        let plan = LayoffPlan::new(&state, &constants);
+        assert_eq!(
+            plan.severance,
+            Decimal::new(40.0) * constants.interns_loc_base_cost * constants.layoff_severance_ratio
+        );
+        state.researched.insert(Research::SmartStaffing);
+        state.loc = plan.severance * Decimal::new(0.5);
+        assert!(!Action::SmartStaffing.is_available(&state, &constants));
+        state.loc = plan.severance;
+        assert!(Action::SmartStaffing.apply(&mut state, &constants));
+        assert_eq!(state.loc, Decimal::ZERO);
+        assert_eq!(devs(&state).to_number().round(), 160.0);
+        // manual and auto hires are laid off alike
+        assert_eq!(state.manual_interns.to_number().round(), 30.0);
+        assert_eq!(
+            state.effective(Stat::SeniorDevsBugsRatio),
+            state.senior_devs_bugs_ratio * plan.bugs_multiplier
+        );
+        state
+            .modifiers
+            .remove_expired(state.game_time + constants.layoff_morale_duration_seconds.to_number());
+        assert_eq!(
+            state.effective(Stat::SeniorDevsBugsRatio),
+            state.senior_devs_bugs_ratio
+        );
+    }
+
+    #[test]
+    fn preview_matches_applying_the_plan() {
+        for policy in [
+            LayoffPolicy::Uniform,
+            LayoffPolicy::KeepSeniors,
+            LayoffPolicy::JuniorsFirst,
+        ] {
+            let (mut state, constants) = staffed(policy);
+            let plan = LayoffPlan::new(&state, &constants);
+            let devs_after = plan.devs_after(&state);
+            let loc_after = plan.loc_after(&Rates::new(&state));
+            state.loc = plan.severance;
+            plan.apply(&mut state, &constants);
+            assert_eq!(
+                devs_after.to_number().round(),
+                devs(&state).to_number().round()
+            );
+            let loc = Rates::new(&state).loc;
+            assert!(loc > Decimal::ZERO);
+            assert!((loc_after - loc).abs() <= loc * Decimal::new(1e-9));
+        }
+    }
+}
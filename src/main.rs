@@ -1,21 +1,62 @@
 #![allow(non_snake_case)]
 
+mod about;
+mod achievements;
+mod actions;
+#[cfg(not(target_arch = "wasm32"))]
+mod balance;
+#[cfg(feature = "cheats")]
 mod cheat_action;
+#[cfg(feature = "cheats")]
 mod cheat_action_data;
+mod cheats;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+mod codebases;
+mod confirmation;
+#[cfg(feature = "cheats")]
+mod console;
 mod constants;
+mod eta;
 mod format_decimal;
+mod format_duration;
+mod git;
+mod hr_quotas;
+#[cfg(test)]
+mod invariant_tests;
+mod keybindings;
+mod layoffs;
 mod metrics;
+mod modifiers;
+mod next_affordable;
+mod presets;
+mod producers;
+mod quest_data;
+mod random_events;
+mod rates;
 mod repeatable_action;
 mod repeatable_action_data;
+mod report;
 mod research_data;
 mod research_once;
+mod research_tree;
 mod resources;
+mod save;
+mod settings;
 mod simple_action;
 mod simple_logs;
+mod simple_rng;
+#[cfg(not(target_arch = "wasm32"))]
+mod simulation;
+mod snapshot;
+#[cfg(not(target_arch = "wasm32"))]
+mod solver;
 mod speedrun;
 mod state;
+mod statistics;
+mod storage;
+mod themes;
 mod toggle_theme_action;
-mod quest_data;
 
 use simple_logs::Logs;
 use toggle_theme_action::ToggleThemeAction;
@@ -24,32 +65,64 @@ use break_infinity::Decimal;
 use dioxus::prelude::*;
 use dioxus_logger::tracing::{info, Level};
 
-use crate::cheat_action_data::CheatActions;
-use crate::constants::{GameConstants};
+use crate::about::About;
+use crate::achievements::Achievements;
+use crate::actions::Action;
+use crate::cheats::{CheatActions, DevConsole};
+use crate::confirmation::{ConfirmationModal, UndoBanner};
+use crate::constants::use_game_constants;
+use crate::keybindings::listen_keys;
 use crate::metrics::Metrics;
+use crate::modifiers::ActiveModifiers;
+use crate::next_affordable::NextAffordable;
+use crate::presets::{build_preset, Preset};
+use crate::quest_data::Quests;
+use crate::random_events::RandomEventModal;
 use crate::repeatable_action_data::RepeatableActions;
 use crate::research_data::Researches;
+use crate::research_tree::ResearchTree;
 use crate::resources::Resources;
+use crate::save::{load_game, save_game};
+use crate::settings::SettingsPanel;
 use crate::simple_action::SimpleAction;
-use crate::speedrun::Speedrun;
-use crate::state::State;
+use crate::speedrun::{Speedrun, SpeedrunHistory};
+use crate::state::{use_game_state, State};
+use crate::statistics::Statistics;
+use crate::themes::restore_theme;
 use async_std::task::sleep;
-use crate::quest_data::Quests;
-
-#[derive(Clone, Debug, PartialEq)]
-#[repr(u8)]
-enum Theme {
-    LightTheme,
-    DarkTheme,
-}
+use std::time::Duration;
+use web_time::Instant;
 
+// every view is rendered inside `Game`, which keeps the simulation running
 #[derive(Clone, Routable, Debug, PartialEq)]
+#[rustfmt::skip]
 enum Route {
-    #[route("/")]
-    Home {},
+    #[layout(Game)]
+        #[route("/")]
+        Home {},
+        #[route("/statistics")]
+        Statistics {},
+        #[route("/achievements")]
+        Achievements {},
+        #[route("/settings")]
+        SettingsPanel {},
+        #[route("/research")]
+        ResearchTree {},
+        #[route("/speedruns")]
+        SpeedrunHistory {},
+        #[route("/about")]
+        About {},
 }
 
 fn main() {
+    // native builds run balance tools when given a command, see `cli`
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        if !args.is_empty() {
+            std::process::exit(cli::run(&args));
+        }
+    }
     // Init logger
     dioxus_logger::init(Level::INFO).expect("failed to init logger");
     info!("starting app");
@@ -63,39 +136,107 @@ fn App() -> Element {
 }
 
 #[component]
-fn Home() -> Element {
-    let constants = GameConstants::default();
+fn Game() -> Element {
+    let constants = use_hook(|| {
+        build_preset()
+            .constants()
+            .expect("presets are valid, see presets tests")
+    });
     let mut state: Signal<State> = use_signal(|| State::new(constants.clone()));
+    // shared with every view, see `use_game_state` and `use_game_constants`
+    use_context_provider(|| state);
+    use_context_provider(|| constants.clone());
 
     use_future(move || async move {
-        let dt_milliseconds = 100; // real time between 2 updates
-        let dt_seconds = Decimal::new(1e3 / dt_milliseconds as f64);
+        restore_theme(state).await;
+    });
+    let keys_constants = constants.clone();
+    use_future(move || listen_keys(state, keys_constants.clone()));
+    use_future(move || async move {
+        load_game(state).await;
+        let mut last_render = Instant::now();
+        let mut last_save = Instant::now();
         loop {
-            state.write().update(dt_seconds);
+            let settings = state.peek().settings.clone();
+            let dt_milliseconds = settings.tick_milliseconds; // real time between 2 updates
+            let dt_seconds = Decimal::new(1e3 / dt_milliseconds as f64);
+            // updated silently, subscribers are only notified at the render rate
+            #[allow(deprecated)]
+            state.write_silent().update(dt_seconds);
+            if last_render.elapsed() >= Duration::from_millis(settings.render_milliseconds) {
+                last_render = Instant::now();
+                // notify subscribers of the updates done silently since the last render
+                drop(state.write());
+            }
+            if settings.autosave_seconds > 0
+                && last_save.elapsed() >= Duration::from_secs(settings.autosave_seconds)
+            {
+                last_save = Instant::now();
+                save_game(&state.peek());
+            }
             // sleep before next tick
-            sleep(std::time::Duration::from_millis(dt_milliseconds)).await;
+            sleep(Duration::from_millis(dt_milliseconds)).await;
         }
     });
 
+    rsx! {
+        NavBar {}
+        RandomEventModal {}
+        ConfirmationModal {}
+        UndoBanner {}
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn NavBar() -> Element {
+    let constants = use_game_constants();
+    let links = [
+        (Route::Home {}, "game"),
+        (Route::Statistics {}, "statistics"),
+        (Route::Achievements {}, "achievements"),
+        (Route::ResearchTree {}, "research tree"),
+        (Route::SpeedrunHistory {}, "speedruns"),
+        (Route::SettingsPanel {}, "settings"),
+        (Route::About {}, "about"),
+    ];
+    rsx! {
+        nav {
+            class: "nav-bar",
+            for (route, label) in links {
+                Link {
+                    to: route,
+                    active_class: "active",
+                    "{label}"
+                }
+            }
+            // players only ever get the normal balance, other presets are for testing
+            if constants.preset != Preset::Normal.name() {
+                span {
+                    class: "preset",
+                    "{constants.preset} preset"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Home() -> Element {
+    let constants = use_game_constants();
+    let state = use_game_state();
     rsx! {
         div { // vertical
             class: "everything",
-            Logs {
-                state: state,
-            }
+            Logs {}
+            DevConsole {}
             div { // vertical
                 class: "metrics",
-                Speedrun {
-                    state: state,
-                    max_loc: constants.quest_differentiation_loc_cost,
-                }
-                Metrics {
-                    state: state,
-                }
+                Speedrun {}
+                Metrics {}
+                ActiveModifiers {}
                 if state.read().loc > Decimal::ZERO {
-                    Resources {
-                        state: state,
-                    }
+                    Resources {}
                 }
             }
             div { // horizontal
@@ -103,44 +244,32 @@ fn Home() -> Element {
                 div { // vertical
                     class: "repeatable-actions",
                     SimpleAction {
-                        state: state,
                         button_name: "code",
-                        action: move |mut s: Signal<State>| {
-                            let loc_added = state.read().loc_per_clicks;
-                            let bugs_added = loc_added * state.read().manual_bugs_ratio;
-                            s.write().loc += loc_added;
-                            s.write().bugs += bugs_added;
+                        action: {
+                            let constants = constants.clone();
+                            move |mut s: Signal<State>| {
+                                Action::Code.apply(&mut s.write(), &constants);
+                            }
                         },
                     }
-                    if state.read().bugs > Decimal::ZERO {
+                    if Action::Debug.is_available(&state.read(), &constants) {
                         SimpleAction {
-                            state: state,
                             button_name: "debug",
-                            action: move |mut s: Signal<State>| {
-                                let bugs_removed = state.read().debug_per_clicks;
-                                s.write().bugs -= bugs_removed;
+                            action: {
+                                let constants = constants.clone();
+                                move |mut s: Signal<State>| {
+                                    Action::Debug.apply(&mut s.write(), &constants);
+                                }
                             },
                         }
                     }
-                    ToggleThemeAction {
-                        state: state,
-                    }
-                    RepeatableActions {
-                        state: state,
-                        constants: constants.clone(),
-                    }
-                    CheatActions {
-                        state: state,
-                    }
-                }
-                Researches {
-                    state: state,
-                    constants: constants.clone(),
-                }
-                Quests {
-                    state: state,
-                    constants: constants.clone(),
+                    ToggleThemeAction {}
+                    RepeatableActions {}
+                    CheatActions {}
                 }
+                Researches {}
+                Quests {}
+                NextAffordable {}
             }
         }
     }
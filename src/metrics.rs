@@ -1,14 +1,211 @@
 #![allow(non_snake_case)]
 use crate::constants::Research;
-use crate::format_decimal::{format_decimal_bugs, format_decimal_features, format_decimal_loc};
-use crate::state::State;
+use crate::format_decimal::{
+    format_decimal_bugs, format_decimal_devs, format_decimal_features, format_decimal_loc,
+    format_decimal_pms,
+};
+use crate::modifiers::Stat;
+use crate::rates::{Rates, TierRates};
+use crate::state::{use_game_state, State};
+use break_infinity::Decimal;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
-use dioxus::prelude::Signal;
 use dioxus::prelude::*;
 
+fn tier_loc_breakdown(
+    state: &State,
+    name: &str,
+    tier: &TierRates,
+    stat: Stat,
+    lines: &mut Vec<String>,
+) {
+    if tier.auto + tier.manual <= Decimal::ZERO {
+        return;
+    }
+    lines.push(format!(
+        "{}: ({} auto + {}) x {} = {}/s",
+        name,
+        format_decimal_devs(tier.auto, state.settings.notation),
+        format_decimal_devs(tier.manual, state.settings.notation),
+        format_decimal_loc(tier.loc_per_dev * state.dt_seconds, state.settings.notation),
+        format_decimal_loc(tier.loc * state.dt_seconds, state.settings.notation),
+    ));
+    lines.push(format!(
+        "    per dev: {}",
+        state.modifiers.explain(stat, state.base(stat))
+    ));
+}
+
+fn tier_bugs_breakdown(
+    state: &State,
+    name: &str,
+    tier: &TierRates,
+    stat: Stat,
+    lines: &mut Vec<String>,
+) {
+    if tier.bugs <= Decimal::ZERO {
+        return;
+    }
+    lines.push(format!(
+        "{}: +{}/s (bugs ratio {})",
+        name,
+        format_decimal_bugs(tier.bugs * state.dt_seconds, state.settings.notation),
+        state.modifiers.explain(stat, state.base(stat)),
+    ));
+}
+
+fn loc_breakdown(state: &State, rates: &Rates) -> String {
+    let mut lines = vec![];
+    tier_loc_breakdown(
+        state,
+        "interns",
+        &rates.interns,
+        Stat::InternsLocDt,
+        &mut lines,
+    );
+    tier_loc_breakdown(
+        state,
+        "junior devs",
+        &rates.junior_devs,
+        Stat::JuniorDevsLocDt,
+        &mut lines,
+    );
+    tier_loc_breakdown(
+        state,
+        "senior devs",
+        &rates.senior_devs,
+        Stat::SeniorDevsLocDt,
+        &mut lines,
+    );
+    if lines.is_empty() {
+        lines.push("nobody is coding for you yet".to_string());
+    }
+    lines.join("\n")
+}
+
+fn bugs_breakdown(state: &State, rates: &Rates) -> String {
+    let mut lines = vec![];
+    tier_bugs_breakdown(
+        state,
+        "interns",
+        &rates.interns,
+        Stat::InternsBugsRatio,
+        &mut lines,
+    );
+    tier_bugs_breakdown(
+        state,
+        "junior devs",
+        &rates.junior_devs,
+        Stat::JuniorDevsBugsRatio,
+        &mut lines,
+    );
+    tier_bugs_breakdown(
+        state,
+        "senior devs",
+        &rates.senior_devs,
+        Stat::SeniorDevsBugsRatio,
+        &mut lines,
+    );
+    if rates.bugs_converted > Decimal::ZERO {
+        lines.push(format!(
+            "PMs: -{}/s",
+            format_decimal_bugs(
+                rates.bugs_converted * state.dt_seconds,
+                state.settings.notation
+            )
+        ));
+    }
+    if lines.is_empty() {
+        lines.push("no bugs yet".to_string());
+    }
+    lines.join("\n")
+}
+
+fn features_breakdown(state: &State, rates: &Rates) -> String {
+    let mut lines = vec![format!(
+        "PMs: {} auto + {}",
+        format_decimal_pms(state.pms, state.settings.notation),
+        format_decimal_pms(state.manual_pms, state.settings.notation),
+    )];
+    lines.push(format!(
+        "capacity: {}/s ({})",
+        format_decimal_bugs(
+            rates.bugs_converted_capacity * state.dt_seconds,
+            state.settings.notation
+        ),
+        state.modifiers.explain(
+            Stat::PmsBugsConversionDt,
+            state.base(Stat::PmsBugsConversionDt)
+        ),
+    ));
+    lines.push(format!(
+        "actual: {}/s",
+        format_decimal_features(
+            rates.bugs_converted * state.dt_seconds,
+            state.settings.notation
+        )
+    ));
+    if rates.bugs_converted < rates.bugs_converted_capacity {
+        lines.push("limited by available bugs".to_string());
+    }
+    lines.join("\n")
+}
+
+fn staff_breakdown(state: &State, rates: &Rates) -> String {
+    let per_second =
+        |amount: Decimal| format_decimal_devs(amount * state.dt_seconds, state.settings.notation);
+    let mut lines = vec![
+        format!("HR hiring interns: {}/s", per_second(rates.hired_interns)),
+        format!(
+            "HR hiring junior devs: {}/s",
+            per_second(rates.hired_junior_devs)
+        ),
+        format!(
+            "HR hiring senior devs: {}/s",
+            per_second(rates.hired_senior_devs)
+        ),
+    ];
+    if state.researched.contains(&Research::RecursiveHR) {
+        lines.push(format!("HR hiring HRs: {}/s", per_second(rates.hired_hrs)));
+    }
+    if rates.hired_pms > Decimal::ZERO {
+        lines.push(format!("HR hiring PMs: {}/s", per_second(rates.hired_pms)));
+    }
+    if state.researched.contains(&Research::InternsPromotion) {
+        lines.push(format!(
+            "interns promoted to junior devs: {}/s",
+            per_second(rates.promoted_interns)
+        ));
+    }
+    if state.researched.contains(&Research::JuniorDevsPromotion) {
+        lines.push(format!(
+            "junior devs promoted to senior devs: {}/s",
+            per_second(rates.promoted_junior_devs)
+        ));
+    }
+    lines.push(format!(
+        "senior devs retiring: {}/s",
+        per_second(rates.retired_senior_devs)
+    ));
+    if rates.senior_devs_becoming_pms > Decimal::ZERO {
+        lines.push(format!(
+            "senior devs becoming PMs: {}/s",
+            per_second(rates.senior_devs_becoming_pms)
+        ));
+    }
+    lines.join("\n")
+}
+
 #[component]
-pub(crate) fn Metrics(state: Signal<State>) -> Element {
+pub(crate) fn Metrics() -> Element {
+    let state = use_game_state();
+    let rates = Rates::new(&state.read());
+    let hired = (rates.hired_interns + rates.hired_junior_devs + rates.hired_senior_devs)
+        * state.read().dt_seconds;
+    let loc_tooltip = loc_breakdown(&state.read(), &rates);
+    let bugs_tooltip = bugs_breakdown(&state.read(), &rates);
+    let features_tooltip = features_breakdown(&state.read(), &rates);
+    let staff_tooltip = staff_breakdown(&state.read(), &rates);
     rsx! {
         if state.read().researched.contains(&Research::CodeMetrics) {
             div {
@@ -26,24 +223,53 @@ pub(crate) fn Metrics(state: Signal<State>) -> Element {
                         }
                     }
                     tr {
+                        class: "tooltip",
                         td {"LOC/s"}
                         td {
                             class: "table-value",
-                            "{format_decimal_loc(state.read().loc_dt)}"
+                            "{format_decimal_loc(state.read().loc_dt, state.read().settings.notation)}"
+                        }
+                        td {
+                            class: "tooltip-text",
+                            "{loc_tooltip}"
                         }
                     }
                     tr {
+                        class: "tooltip",
                         td {"bugs/s"}
                         td {
                             class: "table-value",
-                            "{format_decimal_bugs(state.read().bugs_dt)}"
+                            "{format_decimal_bugs(state.read().bugs_dt, state.read().settings.notation)}"
+                        }
+                        td {
+                            class: "tooltip-text",
+                            "{bugs_tooltip}"
                         }
                     }
                     tr {
+                        class: "tooltip",
                         td {"feature/s"}
                         td {
                             class: "table-value",
-                            "{format_decimal_features(state.read().features_dt)}"
+                            "{format_decimal_features(state.read().features_dt, state.read().settings.notation)}"
+                        }
+                        td {
+                            class: "tooltip-text",
+                            "{features_tooltip}"
+                        }
+                    }
+                    if state.read().hrs + state.read().manual_hrs > Decimal::ZERO {
+                        tr {
+                            class: "tooltip",
+                            td {"hiring/s"}
+                            td {
+                                class: "table-value",
+                                "{format_decimal_devs(hired, state.read().settings.notation)}"
+                            }
+                            td {
+                                class: "tooltip-text",
+                                "{staff_tooltip}"
+                            }
                         }
                     }
                     if state.read().researched.contains(&Research::Cheating) {
@@ -54,6 +280,13 @@ pub(crate) fn Metrics(state: Signal<State>) -> Element {
                                 "{state.read().dt}"
                             }
                         }
+                        tr {
+                            td {"seed"}
+                            td {
+                                class: "table-value",
+                                "{state.read().rng_seed}"
+                            }
+                        }
                     }
                 }
             }
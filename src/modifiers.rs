@@ -0,0 +1,247 @@
+#![allow(non_snake_case)]
+use crate::constants::Research;
+use crate::random_events::RandomEvent;
+use crate::state::use_game_state;
+use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/**
+ * Rates of `State` that modifiers can target.
+ * `State` keeps the base value, `State::effective` applies the modifiers on top of it.
+ */
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[repr(u32)]
+pub(crate) enum Stat {
+    InternsLocDt,
+    JuniorDevsLocDt,
+    SeniorDevsLocDt,
+    InternsBugsRatio,
+    JuniorDevsBugsRatio,
+    SeniorDevsBugsRatio,
+    HrsInternsDt,
+    HrsJuniorDevsDt,
+    HrsSeniorDevsDt,
+    HrsHrsDt,
+    HrsPmsDt,
+    PmsBugsConversionDt,
+    InternsPromotionRatioDt,
+    JuniorDevsPromotionRatioDt,
+    SeniorDevsRetirementRatioDt,
+    SeniorDevsManagementRatioDt,
+}
+
+impl Stat {
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            Stat::InternsLocDt => "interns loc",
+            Stat::JuniorDevsLocDt => "junior devs loc",
+            Stat::SeniorDevsLocDt => "senior devs loc",
+            Stat::InternsBugsRatio => "interns bugs ratio",
+            Stat::JuniorDevsBugsRatio => "junior devs bugs ratio",
+            Stat::SeniorDevsBugsRatio => "senior devs bugs ratio",
+            Stat::HrsInternsDt => "HR interns hiring",
+            Stat::HrsJuniorDevsDt => "HR junior devs hiring",
+            Stat::HrsSeniorDevsDt => "HR senior devs hiring",
+            Stat::HrsHrsDt => "HR HRs hiring",
+            Stat::HrsPmsDt => "HR PMs hiring",
+            Stat::PmsBugsConversionDt => "PM bugs conversion",
+            Stat::InternsPromotionRatioDt => "interns promotion",
+            Stat::JuniorDevsPromotionRatioDt => "junior devs promotion",
+            Stat::SeniorDevsRetirementRatioDt => "senior devs retirement",
+            Stat::SeniorDevsManagementRatioDt => "senior devs to PM",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum Operation {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) enum ModifierSource {
+    Research(Research),
+    Event(RandomEvent),
+    Layoffs,
+}
+
+impl ModifierSource {
+    pub(crate) fn label(&self) -> String {
+        match self {
+            ModifierSource::Research(research) => format!("research {:?}", research),
+            ModifierSource::Event(event) => format!("event {}", event.title()),
+            ModifierSource::Layoffs => "smart staffing".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct Modifier {
+    pub name: String,
+    pub target: Stat,
+    pub operation: Operation,
+    pub value: Decimal,
+    pub source: ModifierSource,
+    // game time at which the modifier is removed, permanent if None
+    pub expires_at: Option<f64>,
+}
+
+impl Modifier {
+    /**
+     * Permanent modifier, set `expires_at` for a timed one
+     */
+    pub(crate) fn new(
+        name: &str,
+        target: Stat,
+        operation: Operation,
+        value: Decimal,
+        source: ModifierSource,
+    ) -> Self {
+        Modifier {
+            name: name.to_string(),
+            target,
+            operation,
+            value,
+            source,
+            expires_at: None,
+        }
+    }
+
+    pub(crate) fn effect(&self) -> String {
+        match self.operation {
+            Operation::Add => format!("+{}", self.value.to_precision(2)),
+            Operation::Multiply => format!("x{}", self.value.to_precision(2)),
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub(crate) struct Modifiers {
+    modifiers: Vec<Modifier>,
+}
+
+impl Modifiers {
+    pub(crate) fn push(&mut self, modifier: Modifier) {
+        self.modifiers.push(modifier);
+    }
+
+    /// removes a single modifier equal to `modifier`, if any
+    pub(crate) fn remove(&mut self, modifier: &Modifier) {
+        if let Some(index) = self.modifiers.iter().position(|other| other == modifier) {
+            self.modifiers.remove(index);
+        }
+    }
+
+    pub(crate) fn remove_expired(&mut self, now: f64) {
+        self.modifiers.retain(|modifier| {
+            modifier
+                .expires_at
+                .is_none_or(|expires_at| now < expires_at)
+        });
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Modifier> {
+        self.modifiers.iter()
+    }
+
+    /**
+     * Human readable explanation of how a stat gets its effective value, e.g. "base 1.00, x2.00 syntax coloring"
+     */
+    pub(crate) fn explain(&self, target: Stat, base: Decimal) -> String {
+        let mut parts = vec![format!("base {}", base.to_precision(2))];
+        // same order as `apply`
+        for operation in [Operation::Add, Operation::Multiply] {
+            parts.extend(
+                self.modifiers
+                    .iter()
+                    .filter(|modifier| modifier.target == target && modifier.operation == operation)
+                    .map(|modifier| format!("{} {}", modifier.effect(), modifier.name)),
+            );
+        }
+        parts.join(", ")
+    }
+
+    /**
+     * Additive modifiers are summed with the base value first, then multiplicative ones are applied,
+     * so that the result does not depend on the order modifiers were acquired.
+     */
+    pub(crate) fn apply(&self, target: Stat, base: Decimal) -> Decimal {
+        let targeted = || {
+            self.modifiers
+                .iter()
+                .filter(move |modifier| modifier.target == target)
+        };
+        let added = targeted()
+            .filter(|modifier| modifier.operation == Operation::Add)
+            .fold(base, |value, modifier| value + modifier.value);
+        targeted()
+            .filter(|modifier| modifier.operation == Operation::Multiply)
+            .fold(added, |value, modifier| value * modifier.value)
+    }
+}
+
+#[component]
+pub(crate) fn ActiveModifiers() -> Element {
+    let state = use_game_state();
+    let game_time = state.read().game_time;
+    let rows: Vec<(String, String, String, String)> = state
+        .read()
+        .modifiers
+        .iter()
+        .map(|modifier| {
+            let remaining = modifier
+                .expires_at
+                .map(|expires_at| format!("{:.0}s", (expires_at - game_time).max(0.0)))
+                .unwrap_or_default();
+            (
+                modifier.name.clone(),
+                modifier.source.label(),
+                format!("{} {}", modifier.target.label(), modifier.effect()),
+                remaining,
+            )
+        })
+        .collect();
+    rsx! {
+        if !rows.is_empty() {
+            div {
+                class: "metrics",
+                table {
+                    class: "metrics-table",
+                    tr {
+                        th {
+                            class: "table-name",
+                            "modifiers"
+                        }
+                        th {
+                            class: "table-name",
+                            "source"
+                        }
+                        th {
+                            class: "table-name",
+                            "effect"
+                        }
+                        th {
+                            class: "table-value",
+                            "remaining"
+                        }
+                    }
+                    for (name, source, effect, remaining) in rows {
+                        tr {
+                            td {"{name}"}
+                            td {"{source}"}
+                            td {"{effect}"}
+                            td {
+                                class: "table-value",
+                                "{remaining}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+#![allow(non_snake_case)]
+use crate::constants::{use_game_constants, GameConstants};
+use crate::eta::seconds_until_affordable;
+use crate::format_decimal::format_decimal_loc;
+use crate::format_duration::format_duration;
+use crate::producers::Producer;
+use crate::quest_data::quest_definitions;
+use crate::research_data::research_definitions;
+use crate::state::{use_game_state, State};
+use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+
+const MAX_PURCHASES: usize = 10;
+
+/**
+ * Every purchase currently available (hires, researches, quests), soonest affordable first
+ */
+fn next_purchases(state: &State, constants: &GameConstants) -> Vec<(String, Decimal, Option<f64>)> {
+    let mut purchases = vec![];
+    for producer in Producer::ALL {
+        if state.researched.contains(&producer.require()) {
+            let loc_cost = producer.next_loc_cost(state, constants);
+            purchases.push((
+                format!("hire {}", producer.name()),
+                loc_cost,
+                seconds_until_affordable(loc_cost, state),
+            ));
+        }
+    }
+    for definition in research_definitions(constants)
+        .into_iter()
+        .chain(quest_definitions(constants))
+    {
+        let requirements_met = definition
+            .require
+            .is_none_or(|required| state.researched.contains(&required));
+        if requirements_met && !state.researched.contains(&definition.research) {
+            purchases.push((
+                definition.button_name.to_string(),
+                definition.loc_cost,
+                seconds_until_affordable(definition.loc_cost, state),
+            ));
+        }
+    }
+    purchases.sort_by(|(_, _, a), (_, _, b)| {
+        a.unwrap_or(f64::INFINITY)
+            .total_cmp(&b.unwrap_or(f64::INFINITY))
+    });
+    purchases.truncate(MAX_PURCHASES);
+    purchases
+}
+
+#[component]
+pub(crate) fn NextAffordable() -> Element {
+    let state = use_game_state();
+    let constants = use_game_constants();
+    let purchases: Vec<(String, String, String)> = next_purchases(&state.read(), &constants)
+        .into_iter()
+        .map(|(name, loc_cost, seconds)| {
+            let eta = match seconds {
+                Some(seconds) if seconds <= 0.0 => "now".to_string(),
+                Some(seconds) => format_duration(seconds),
+                None => "never".to_string(),
+            };
+            (
+                name,
+                format_decimal_loc(loc_cost, state.read().settings.notation),
+                eta,
+            )
+        })
+        .collect();
+    rsx! {
+        if !purchases.is_empty() {
+            div {
+                class: "next-affordable",
+                table {
+                    tr {
+                        th {
+                            class: "table-name",
+                            "next affordable"
+                        }
+                        th {
+                            class: "table-value",
+                            "cost"
+                        }
+                        th {
+                            class: "table-value",
+                            "in"
+                        }
+                    }
+                    for (name, loc_cost, eta) in purchases {
+                        tr {
+                            td {"{name}"}
+                            td {
+                                class: "table-value",
+                                "{loc_cost}"
+                            }
+                            td {
+                                class: "table-value",
+                                "{eta}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
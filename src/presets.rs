@@ -0,0 +1,167 @@
+//! Balance presets: game constants written as TOML files in `presets/`.
+//! Presets are embedded at build time, native tools may also load constants from any file.
+
+use crate::constants::GameConstants;
+use break_infinity::Decimal;
+use toml::{Table, Value};
+
+// key naming the preset a file starts from, instead of giving every constant
+const INHERITS: &str = "inherits";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Preset {
+    Normal,
+    // about a hundred times faster, for QA
+    FastTest,
+    Hardcore,
+}
+
+impl Preset {
+    pub(crate) const ALL: [Preset; 3] = [Preset::Normal, Preset::FastTest, Preset::Hardcore];
+
+    pub(crate) const fn name(&self) -> &'static str {
+        match self {
+            Preset::Normal => "normal",
+            Preset::FastTest => "fast-test",
+            Preset::Hardcore => "hardcore",
+        }
+    }
+
+    // const to check `REASONABLY_PRESET` at compile time
+    pub(crate) const fn from_name(name: &str) -> Option<Preset> {
+        let mut index = 0;
+        while index < Preset::ALL.len() {
+            if same_name(Preset::ALL[index].name(), name) {
+                return Some(Preset::ALL[index]);
+            }
+            index += 1;
+        }
+        None
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Preset::Normal => include_str!("../presets/normal.toml"),
+            Preset::FastTest => include_str!("../presets/fast-test.toml"),
+            Preset::Hardcore => include_str!("../presets/hardcore.toml"),
+        }
+    }
+
+    pub(crate) fn constants(&self) -> Result<GameConstants, String> {
+        parse_constants(self.name(), self.source())
+    }
+}
+
+/// `==` on strings, which is not const
+const fn same_name(left: &str, right: &str) -> bool {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    if left.len() != right.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < left.len() {
+        if left[index] != right[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+// evaluated by the compiler, an unknown name fails the build instead of the game at startup
+const BUILD_PRESET: Preset = match option_env!("REASONABLY_PRESET") {
+    None => Preset::Normal,
+    Some(name) => match Preset::from_name(name) {
+        Some(preset) => preset,
+        None => {
+            panic!("unknown preset in REASONABLY_PRESET, expected normal, fast-test or hardcore")
+        }
+    },
+};
+
+/**
+ * Preset the game is built with, chosen with the `REASONABLY_PRESET` environment variable
+ * at compile time: `REASONABLY_PRESET=fast-test dx serve`
+ */
+pub(crate) fn build_preset() -> Preset {
+    BUILD_PRESET
+}
+
+/// constants of a file, inherited ones included, still as TOML values
+fn values(name: &str, source: &str) -> Result<Table, String> {
+    let mut table: Table = source
+        .parse()
+        .map_err(|error| format!("invalid constants in {}: {}", name, error))?;
+    let mut values = match table.remove(INHERITS) {
+        None => Table::new(),
+        Some(Value::String(parent)) => {
+            let preset = Preset::from_name(&parent)
+                .ok_or_else(|| format!("{} inherits unknown preset {}", name, parent))?;
+            values(preset.name(), preset.source())?
+        }
+        Some(_) => return Err(format!("{} must inherit a preset name", name)),
+    };
+    values.extend(table);
+    Ok(values)
+}
+
+/**
+ * Constants of the TOML `source`, named `name` in errors and in the UI.
+ * Every constant must be given, either by the file or by the preset it inherits.
+ */
+pub(crate) fn parse_constants(name: &str, source: &str) -> Result<GameConstants, String> {
+    let mut decimals = Table::new();
+    for (key, value) in values(name, source)? {
+        let number = match value {
+            Value::Float(number) => number,
+            Value::Integer(number) => number as f64,
+            _ => return Err(format!("{} in {} must be a number", key, name)),
+        };
+        let decimal = Value::try_from(Decimal::new(number)).map_err(|error| error.to_string())?;
+        decimals.insert(key, decimal);
+    }
+    let mut constants: GameConstants = Value::Table(decimals)
+        .try_into()
+        .map_err(|error| format!("invalid constants in {}: {}", name, error))?;
+    constants.preset = name.to_string();
+    constants
+        .validate()
+        .map_err(|errors| format!("invalid constants in {}:\n  {}", name, errors.join("\n  ")))?;
+    Ok(constants)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_preset_is_valid() {
+        for preset in Preset::ALL {
+            let constants = preset
+                .constants()
+                .unwrap_or_else(|error| panic!("{}", error));
+            assert_eq!(constants.preset, preset.name());
+        }
+    }
+
+    #[test]
+    fn files_inherit_presets_and_report_mistakes() {
+        let constants =
+            parse_constants("tweak", "inherits = \"normal\"\nloc_per_clicks = 3").unwrap();
+        assert_eq!(constants.loc_per_clicks, Decimal::new(3.0));
+        assert_eq!(constants.dt, GameConstants::default().dt);
+
+        let missing = parse_constants("partial", "loc_per_clicks = 3").unwrap_err();
+        assert!(missing.contains("missing field"), "{}", missing);
+        let unknown =
+            parse_constants("typo", "inherits = \"normal\"\nloc_per_click = 3").unwrap_err();
+        assert!(unknown.contains("loc_per_click"), "{}", unknown);
+        let invalid = parse_constants(
+            "invalid",
+            "inherits = \"normal\"\ninterns_loc_growth_rate = 0.9\nhrs_interns_quota = 2",
+        )
+        .unwrap_err();
+        assert!(invalid.contains("interns_loc_growth_rate"), "{}", invalid);
+        assert!(invalid.contains("hrs_interns_quota"), "{}", invalid);
+    }
+}
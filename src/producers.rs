@@ -0,0 +1,137 @@
+use crate::constants::{GameConstants, Research};
+use crate::format_decimal::{
+    format_decimal_devs, format_decimal_hrs, format_decimal_pms, Notation,
+};
+use crate::state::State;
+use break_infinity::Decimal;
+use serde::{Deserialize, Serialize};
+
+/**
+ * Roles that can be hired, either manually (for loc) or automatically (by HR, promotions...)
+ */
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[repr(u32)]
+pub(crate) enum Producer {
+    Interns,
+    JuniorDevs,
+    SeniorDevs,
+    Hrs,
+    Pms,
+}
+
+impl Producer {
+    pub(crate) const ALL: [Producer; 5] = [
+        Producer::Interns,
+        Producer::JuniorDevs,
+        Producer::SeniorDevs,
+        Producer::Hrs,
+        Producer::Pms,
+    ];
+
+    // tiers producing loc, from the newest hires to the most experienced
+    pub(crate) const DEVS: [Producer; 3] = [
+        Producer::Interns,
+        Producer::JuniorDevs,
+        Producer::SeniorDevs,
+    ];
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Producer::Interns => "Interns",
+            Producer::JuniorDevs => "Junior devs",
+            Producer::SeniorDevs => "Senior devs",
+            Producer::Hrs => "HRs",
+            Producer::Pms => "PMs",
+        }
+    }
+
+    pub(crate) fn require(&self) -> Research {
+        match self {
+            Producer::Interns => Research::Internship,
+            Producer::JuniorDevs => Research::JuniorDevsPosition,
+            Producer::SeniorDevs => Research::SeniorDevsPosition,
+            Producer::Hrs => Research::HumanResources,
+            Producer::Pms => Research::ProjectManagement,
+        }
+    }
+
+    pub(crate) fn format_headcount(&self, headcount: Decimal, notation: Notation) -> String {
+        match self {
+            Producer::Interns | Producer::JuniorDevs | Producer::SeniorDevs => {
+                format_decimal_devs(headcount, notation)
+            }
+            Producer::Hrs => format_decimal_hrs(headcount, notation),
+            Producer::Pms => format_decimal_pms(headcount, notation),
+        }
+    }
+
+    /// headcount hired automatically (HR, promotions, management career)
+    pub(crate) fn auto(&self, state: &State) -> Decimal {
+        match self {
+            Producer::Interns => state.interns,
+            Producer::JuniorDevs => state.junior_devs,
+            Producer::SeniorDevs => state.senior_devs,
+            Producer::Hrs => state.hrs,
+            Producer::Pms => state.pms,
+        }
+    }
+
+    pub(crate) fn auto_mut<'a>(&self, state: &'a mut State) -> &'a mut Decimal {
+        match self {
+            Producer::Interns => &mut state.interns,
+            Producer::JuniorDevs => &mut state.junior_devs,
+            Producer::SeniorDevs => &mut state.senior_devs,
+            Producer::Hrs => &mut state.hrs,
+            Producer::Pms => &mut state.pms,
+        }
+    }
+
+    /// headcount hired manually, which drives the hiring cost
+    pub(crate) fn manual(&self, state: &State) -> Decimal {
+        match self {
+            Producer::Interns => state.manual_interns,
+            Producer::JuniorDevs => state.manual_junior_devs,
+            Producer::SeniorDevs => state.manual_senior_devs,
+            Producer::Hrs => state.manual_hrs,
+            Producer::Pms => state.manual_pms,
+        }
+    }
+
+    pub(crate) fn manual_mut<'a>(&self, state: &'a mut State) -> &'a mut Decimal {
+        match self {
+            Producer::Interns => &mut state.manual_interns,
+            Producer::JuniorDevs => &mut state.manual_junior_devs,
+            Producer::SeniorDevs => &mut state.manual_senior_devs,
+            Producer::Hrs => &mut state.manual_hrs,
+            Producer::Pms => &mut state.manual_pms,
+        }
+    }
+
+    pub(crate) fn loc_base_cost(&self, constants: &GameConstants) -> Decimal {
+        match self {
+            Producer::Interns => constants.interns_loc_base_cost,
+            Producer::JuniorDevs => constants.junior_devs_loc_base_cost,
+            Producer::SeniorDevs => constants.senior_devs_loc_base_cost,
+            Producer::Hrs => constants.hrs_loc_base_cost,
+            Producer::Pms => constants.pms_loc_base_cost,
+        }
+    }
+
+    pub(crate) fn loc_growth_rate(&self, constants: &GameConstants) -> Decimal {
+        match self {
+            Producer::Interns => constants.interns_loc_growth_rate,
+            Producer::JuniorDevs => constants.junior_devs_loc_growth_rate,
+            Producer::SeniorDevs => constants.senior_devs_loc_growth_rate,
+            Producer::Hrs => constants.hrs_loc_growth_rate,
+            Producer::Pms => constants.pms_loc_growth_rate,
+        }
+    }
+
+    /// loc cost of the next manual hire, same formula as `RepeatableAction`
+    pub(crate) fn next_loc_cost(&self, state: &State, constants: &GameConstants) -> Decimal {
+        self.loc_base_cost(constants)
+            * self
+                .loc_growth_rate(constants)
+                .pow(&(self.manual(state) + Decimal::ONE))
+    }
+}
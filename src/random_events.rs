@@ -0,0 +1,360 @@
+#![allow(non_snake_case)]
+use crate::actions::at_least;
+use crate::constants::{use_game_constants, GameConstants};
+use crate::format_decimal::format_decimal_loc;
+use crate::modifiers::{Modifier, ModifierSource, Operation, Stat};
+use crate::simple_logs::LogCategory;
+use crate::simple_rng::SimpleRng;
+use crate::state::{use_game_state, State};
+use break_infinity::Decimal;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use dioxus::prelude::Writable;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[repr(u32)]
+pub(crate) enum RandomEvent {
+    ProductionOutage,
+    InternDeletesProd,
+    ConferenceTalk,
+    ViralLaunch,
+    Audit,
+}
+
+pub(crate) struct RandomEventChoice {
+    pub label: String,
+    pub loc_cost: Decimal,
+}
+
+impl RandomEvent {
+    pub(crate) const ALL: [RandomEvent; 5] = [
+        RandomEvent::ProductionOutage,
+        RandomEvent::InternDeletesProd,
+        RandomEvent::ConferenceTalk,
+        RandomEvent::ViralLaunch,
+        RandomEvent::Audit,
+    ];
+
+    pub(crate) fn title(&self) -> &'static str {
+        match self {
+            RandomEvent::ProductionOutage => "Production outage",
+            RandomEvent::InternDeletesProd => "An intern deleted prod",
+            RandomEvent::ConferenceTalk => "Conference talk",
+            RandomEvent::ViralLaunch => "Viral launch",
+            RandomEvent::Audit => "Security audit",
+        }
+    }
+
+    pub(crate) fn description(&self) -> &'static str {
+        match self {
+            RandomEvent::ProductionOutage => "Everything is on fire, customers are leaving.",
+            RandomEvent::InternDeletesProd => {
+                "Someone ran the migration script on the wrong database. Backups are partial."
+            }
+            RandomEvent::ConferenceTalk => {
+                "You have been invited to talk at a conference, great for recruiting."
+            }
+            RandomEvent::ViralLaunch => "Your last release is trending, traffic is exploding.",
+            RandomEvent::Audit => "The auditors found a few (hundred) issues.",
+        }
+    }
+
+    /**
+     * The last choice is always free: it is the one applied when the event is dismissed.
+     */
+    pub(crate) fn choices(
+        &self,
+        state: &State,
+        constants: &GameConstants,
+    ) -> Vec<RandomEventChoice> {
+        match self {
+            RandomEvent::ProductionOutage => {
+                let loc_cost = state.loc_dt * constants.event_production_outage_loc_seconds;
+                vec![
+                    RandomEventChoice {
+                        label: format!(
+                            "fix it for {}",
+                            format_decimal_loc(loc_cost, state.settings.notation)
+                        ),
+                        loc_cost,
+                    },
+                    RandomEventChoice {
+                        label: format!(
+                            "lose {}% features",
+                            constants.event_production_outage_features_loss_ratio
+                                * Decimal::new(100.0)
+                        ),
+                        loc_cost: Decimal::ZERO,
+                    },
+                ]
+            }
+            RandomEvent::InternDeletesProd => vec![RandomEventChoice {
+                label: format!(
+                    "restore what is left ({}% loc and bugs lost)",
+                    constants.event_intern_deletes_prod_loss_ratio * Decimal::new(100.0)
+                ),
+                loc_cost: Decimal::ZERO,
+            }],
+            RandomEvent::ConferenceTalk => vec![
+                RandomEventChoice {
+                    label: format!(
+                        "give the talk (hiring x{} for {}s)",
+                        constants.event_conference_talk_hiring_multiplier,
+                        constants.event_conference_talk_duration_seconds
+                    ),
+                    loc_cost: Decimal::ZERO,
+                },
+                RandomEventChoice {
+                    label: "stay home".to_string(),
+                    loc_cost: Decimal::ZERO,
+                },
+            ],
+            RandomEvent::ViralLaunch => {
+                let loc_cost = state.loc_dt * constants.event_viral_launch_loc_seconds;
+                vec![
+                    RandomEventChoice {
+                        label: format!(
+                            "scale up for {} (+{}% features)",
+                            format_decimal_loc(loc_cost, state.settings.notation),
+                            constants.event_viral_launch_features_ratio * Decimal::new(100.0)
+                        ),
+                        loc_cost,
+                    },
+                    RandomEventChoice {
+                        label: format!(
+                            "let it ride (+{}% features, +{}% bugs)",
+                            constants.event_viral_launch_features_ratio * Decimal::new(100.0),
+                            constants.event_viral_launch_bugs_ratio * Decimal::new(100.0)
+                        ),
+                        loc_cost: Decimal::ZERO,
+                    },
+                ]
+            }
+            RandomEvent::Audit => {
+                let loc_cost = state.loc_dt * constants.event_audit_loc_seconds;
+                vec![
+                    RandomEventChoice {
+                        label: format!(
+                            "fix the findings for {} (-{}% bugs)",
+                            format_decimal_loc(loc_cost, state.settings.notation),
+                            constants.event_audit_bugs_ratio * Decimal::new(100.0)
+                        ),
+                        loc_cost,
+                    },
+                    RandomEventChoice {
+                        label: format!(
+                            "ignore the report (+{}% bugs)",
+                            constants.event_audit_bugs_ratio * Decimal::new(100.0)
+                        ),
+                        loc_cost: Decimal::ZERO,
+                    },
+                ]
+            }
+        }
+    }
+
+    /**
+     * Applies `choice`, the event stays pending if its cost is no longer affordable
+     * (costs follow loc/s, which may have grown since the modal was rendered)
+     */
+    pub(crate) fn resolve(
+        &self,
+        choice: usize,
+        state: &mut State,
+        constants: &GameConstants,
+    ) -> bool {
+        let choices = self.choices(state, constants);
+        let choice = choice.min(choices.len() - 1);
+        if !at_least(&state.loc, &choices[choice].loc_cost) {
+            return false;
+        }
+        state.spend(choices[choice].loc_cost);
+        match (self, choice) {
+            (RandomEvent::ProductionOutage, 0) => {}
+            (RandomEvent::ProductionOutage, _) => {
+                state.features *=
+                    Decimal::ONE - constants.event_production_outage_features_loss_ratio;
+            }
+            (RandomEvent::InternDeletesProd, _) => {
+                let remaining_ratio = Decimal::ONE - constants.event_intern_deletes_prod_loss_ratio;
+                state.loc *= remaining_ratio;
+                state.bugs *= remaining_ratio;
+            }
+            (RandomEvent::ConferenceTalk, 0) => {
+                let expires_at =
+                    state.game_time + constants.event_conference_talk_duration_seconds.to_number();
+                for target in [
+                    Stat::HrsInternsDt,
+                    Stat::HrsJuniorDevsDt,
+                    Stat::HrsSeniorDevsDt,
+                    Stat::HrsPmsDt,
+                ] {
+                    state.modifiers.push(Modifier {
+                        expires_at: Some(expires_at),
+                        ..Modifier::new(
+                            "conference talk",
+                            target,
+                            Operation::Multiply,
+                            constants.event_conference_talk_hiring_multiplier,
+                            ModifierSource::Event(*self),
+                        )
+                    });
+                }
+            }
+            (RandomEvent::ConferenceTalk, _) => {}
+            (RandomEvent::ViralLaunch, 0) => {
+                state.features *= Decimal::ONE + constants.event_viral_launch_features_ratio;
+            }
+            (RandomEvent::ViralLaunch, _) => {
+                state.features *= Decimal::ONE + constants.event_viral_launch_features_ratio;
+                state.bugs *= Decimal::ONE + constants.event_viral_launch_bugs_ratio;
+            }
+            (RandomEvent::Audit, 0) => {
+                state.bugs *= Decimal::ONE - constants.event_audit_bugs_ratio;
+            }
+            (RandomEvent::Audit, _) => {
+                state.bugs *= Decimal::ONE + constants.event_audit_bugs_ratio;
+            }
+        }
+        state.logs.log(
+            LogCategory::Event,
+            &format!("{}: {}", self.title(), choices[choice].label),
+        );
+        state.random_events.pending = None;
+        state
+            .random_events
+            .schedule_next(state.game_time, &mut state.rng);
+        true
+    }
+}
+
+/**
+ * Fires a random event every few minutes of game time, one at a time:
+ * the next one is only scheduled once the pending one has been resolved.
+ */
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct RandomEvents {
+    pub pending: Option<RandomEvent>,
+    pub next_event_time: f64,
+    min_interval_seconds: f64,
+    max_interval_seconds: f64,
+}
+
+impl RandomEvents {
+    pub(crate) fn new(constants: &GameConstants, rng: &mut SimpleRng) -> Self {
+        let mut random_events = RandomEvents {
+            pending: None,
+            next_event_time: 0.0,
+            min_interval_seconds: constants.random_events_min_interval_seconds.to_number(),
+            max_interval_seconds: constants.random_events_max_interval_seconds.to_number(),
+        };
+        random_events.schedule_next(0.0, rng);
+        random_events
+    }
+
+    pub(crate) fn schedule_next(&mut self, now: f64, rng: &mut SimpleRng) {
+        self.next_event_time =
+            now + rng.range_f64(self.min_interval_seconds, self.max_interval_seconds);
+    }
+}
+
+#[component]
+pub(crate) fn RandomEventModal() -> Element {
+    let mut state = use_game_state();
+    let constants = use_game_constants();
+    let event = state.read().random_events.pending?;
+    let choices = event.choices(&state.read(), &constants);
+    let default_choice = choices.len() - 1;
+    let dismiss_constants = constants.clone();
+    let buttons = choices.into_iter().enumerate().map(|(index, choice)| {
+        let constants = constants.clone();
+        rsx! {
+            button {
+                class: "repeatable-action-button",
+                disabled: state.read().loc < choice.loc_cost,
+                onclick: move |_| {
+                    event.resolve(index, &mut state.write(), &constants);
+                },
+                "{choice.label}"
+            }
+        }
+    });
+    rsx! {
+        div {
+            class: "modal-backdrop",
+            div {
+                class: "modal",
+                button {
+                    class: "modal-close",
+                    onclick: move |_| {
+                        event.resolve(default_choice, &mut state.write(), &dismiss_constants);
+                    },
+                    "×"
+                }
+                h3 {"{event.title()}"}
+                p {"{event.description()}"}
+                {buttons}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::Research;
+
+    #[test]
+    fn events_fire_one_at_a_time_within_the_interval() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        let min = constants.random_events_min_interval_seconds.to_number();
+        let max = constants.random_events_max_interval_seconds.to_number();
+        let first = state.random_events.next_event_time;
+        assert!((min..=max).contains(&first), "{}", first);
+
+        state.game_time = first;
+        state.update(Decimal::ONE);
+        // nothing fires before the first research
+        assert_eq!(state.random_events.pending, None);
+        state.researched.insert(Research::Internship);
+        state.update(Decimal::ONE);
+        let Some(event) = state.random_events.pending else {
+            panic!("no event fired at {}", state.game_time);
+        };
+        state.update(Decimal::ONE);
+        assert_eq!(state.random_events.pending, Some(event));
+
+        let free = event.choices(&state, &constants).len() - 1;
+        assert!(event.resolve(free, &mut state, &constants));
+        assert_eq!(state.random_events.pending, None);
+        let next = state.random_events.next_event_time - state.game_time;
+        assert!((min..=max).contains(&next), "{}", next);
+    }
+
+    #[test]
+    fn unaffordable_choices_are_refused() {
+        let constants = GameConstants::default();
+        let mut state = State::new(constants.clone());
+        state.random_events.pending = Some(RandomEvent::Audit);
+        state.loc_dt = Decimal::new(100.0);
+        state.loc = Decimal::new(10.0);
+        state.bugs = Decimal::new(1000.0);
+        assert!(!RandomEvent::Audit.resolve(0, &mut state, &constants));
+        assert_eq!(state.loc, Decimal::new(10.0));
+        assert_eq!(state.bugs, Decimal::new(1000.0));
+        assert_eq!(state.random_events.pending, Some(RandomEvent::Audit));
+
+        let loc_cost = state.loc_dt * constants.event_audit_loc_seconds;
+        state.loc = loc_cost;
+        assert!(RandomEvent::Audit.resolve(0, &mut state, &constants));
+        assert_eq!(state.loc, Decimal::ZERO);
+        assert_eq!(
+            state.bugs,
+            Decimal::new(1000.0) * (Decimal::ONE - constants.event_audit_bugs_ratio)
+        );
+        assert_eq!(state.random_events.pending, None);
+    }
+}
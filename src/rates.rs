@@ -0,0 +1,160 @@
+use crate::constants::Research;
+use crate::modifiers::Stat;
+use crate::state::State;
+use break_infinity::Decimal;
+
+/**
+ * Production of a single dev tier during one tick
+ */
+pub(crate) struct TierRates {
+    pub auto: Decimal,
+    pub manual: Decimal,
+    // effective loc produced by a single dev, per tick
+    pub loc_per_dev: Decimal,
+    pub loc: Decimal,
+    pub bugs: Decimal,
+}
+
+impl TierRates {
+    fn new(
+        auto: Decimal,
+        manual: Decimal,
+        loc_dt: Decimal,
+        bugs_ratio: Decimal,
+        dt: Decimal,
+    ) -> Self {
+        let loc_per_dev = loc_dt * dt;
+        let loc = (auto + manual) * loc_per_dev;
+        TierRates {
+            auto,
+            manual,
+            loc_per_dev,
+            loc,
+            bugs: loc * bugs_ratio,
+        }
+    }
+}
+
+/**
+ * Everything produced, converted, hired or moved around during one tick.
+ * `State::update` applies it, metrics tooltips display it: both always agree.
+ */
+pub(crate) struct Rates {
+    pub interns: TierRates,
+    pub junior_devs: TierRates,
+    pub senior_devs: TierRates,
+    pub loc: Decimal,
+    pub bugs: Decimal,
+    pub bugs_converted_capacity: Decimal,
+    pub bugs_converted: Decimal,
+    pub hired_interns: Decimal,
+    pub hired_junior_devs: Decimal,
+    pub hired_senior_devs: Decimal,
+    pub hired_hrs: Decimal,
+    pub hired_pms: Decimal,
+    // ratio of each tier leaving it during the tick, at most 1 even with a huge dt
+    pub interns_promotion_ratio: Decimal,
+    pub junior_devs_promotion_ratio: Decimal,
+    pub senior_devs_retirement_ratio: Decimal,
+    pub senior_devs_management_ratio: Decimal,
+    pub promoted_interns: Decimal,
+    pub promoted_junior_devs: Decimal,
+    pub retired_senior_devs: Decimal,
+    pub senior_devs_becoming_pms: Decimal,
+}
+
+impl Rates {
+    pub(crate) fn new(state: &State) -> Self {
+        let dt = state.dt;
+        let interns = TierRates::new(
+            state.interns,
+            state.manual_interns,
+            state.effective(Stat::InternsLocDt),
+            state.effective(Stat::InternsBugsRatio),
+            dt,
+        );
+        let junior_devs = TierRates::new(
+            state.junior_devs,
+            state.manual_junior_devs,
+            state.effective(Stat::JuniorDevsLocDt),
+            state.effective(Stat::JuniorDevsBugsRatio),
+            dt,
+        );
+        let senior_devs = TierRates::new(
+            state.senior_devs,
+            state.manual_senior_devs,
+            state.effective(Stat::SeniorDevsLocDt),
+            state.effective(Stat::SeniorDevsBugsRatio),
+            dt,
+        );
+        let loc = interns.loc + junior_devs.loc + senior_devs.loc;
+        let bugs = interns.bugs + junior_devs.bugs + senior_devs.bugs;
+
+        let bugs_converted_capacity =
+            (state.pms + state.manual_pms) * state.effective(Stat::PmsBugsConversionDt) * dt;
+        // make sure we do not convert more bugs than available
+        let bugs_converted = state.bugs.min(&bugs_converted_capacity);
+
+        let hrs = state.hrs + state.manual_hrs;
+        let hired_interns =
+            hrs * state.effective(Stat::HrsInternsDt) * state.hrs_interns_quota * dt;
+        let hired_junior_devs =
+            hrs * state.effective(Stat::HrsJuniorDevsDt) * state.hrs_junior_devs_quota * dt;
+        let hired_senior_devs =
+            hrs * state.effective(Stat::HrsSeniorDevsDt) * state.hrs_senior_devs_quota * dt;
+        let hired_hrs = if state.researched.contains(&Research::RecursiveHR) {
+            hrs * state.effective(Stat::HrsHrsDt) * state.hrs_hrs_quota * dt
+        } else {
+            Decimal::ZERO
+        };
+        let hired_pms = if state.researched.contains(&Research::ProjectManagement) {
+            hrs * state.effective(Stat::HrsPmsDt) * state.hrs_pms_quota * dt
+        } else {
+            Decimal::ZERO
+        };
+
+        let interns_promotion_ratio = if state.researched.contains(&Research::InternsPromotion) {
+            (state.effective(Stat::InternsPromotionRatioDt) * dt).min(&Decimal::ONE)
+        } else {
+            Decimal::ZERO
+        };
+        let junior_devs_promotion_ratio =
+            if state.researched.contains(&Research::JuniorDevsPromotion) {
+                (state.effective(Stat::JuniorDevsPromotionRatioDt) * dt).min(&Decimal::ONE)
+            } else {
+                Decimal::ZERO
+            };
+        let senior_devs_retirement_ratio =
+            (state.effective(Stat::SeniorDevsRetirementRatioDt) * dt).min(&Decimal::ONE);
+        // senior devs either retire or become PMs, never both
+        let senior_devs_management_ratio = (state.effective(Stat::SeniorDevsManagementRatioDt)
+            * dt)
+            .min(&(Decimal::ONE - senior_devs_retirement_ratio));
+
+        Rates {
+            promoted_interns: (interns.auto + interns.manual) * interns_promotion_ratio,
+            promoted_junior_devs: (junior_devs.auto + junior_devs.manual)
+                * junior_devs_promotion_ratio,
+            retired_senior_devs: (senior_devs.auto + senior_devs.manual)
+                * senior_devs_retirement_ratio,
+            senior_devs_becoming_pms: (senior_devs.auto + senior_devs.manual)
+                * senior_devs_management_ratio,
+            interns,
+            junior_devs,
+            senior_devs,
+            loc,
+            bugs,
+            bugs_converted_capacity,
+            bugs_converted,
+            hired_interns,
+            hired_junior_devs,
+            hired_senior_devs,
+            hired_hrs,
+            hired_pms,
+            interns_promotion_ratio,
+            junior_devs_promotion_ratio,
+            senior_devs_retirement_ratio,
+            senior_devs_management_ratio,
+        }
+    }
+}
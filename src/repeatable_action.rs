@@ -6,21 +6,24 @@ use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
 use dioxus::prelude::{Signal, Writable};
 
+use crate::eta::{format_eta, seconds_until_affordable};
 use crate::format_decimal::format_decimal_loc;
-use crate::state::State;
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
 
 #[component]
 pub(crate) fn RepeatableAction(
-    mut state: Signal<State>,
     require: Option<Research>,
     produced: Option<Decimal>,
     button_name: String,
-    debug_message: String,
+    // logged on click, actions asking for a confirmation log themselves once performed
+    debug_message: Option<String>,
     description: String,
     loc_base_cost: Decimal,
     loc_growth_rate: Decimal,
     action: EventHandler<Signal<State>>,
 ) -> Element {
+    let mut state = use_game_state();
     let requirements_met = require.map_or_else(
         || true,
         |research_name_required| state.read().researched.contains(&research_name_required),
@@ -32,18 +35,24 @@ pub(crate) fn RepeatableAction(
     };
     let loc_cost = loc_base_cost * loc_growth_rate.pow(&new_instances);
     let disabled = state.read().loc < loc_cost;
+    let eta = format_eta(seconds_until_affordable(loc_cost, &state.read()));
     rsx! {
         if requirements_met {
             div {
                 class: "repeatable-action",
                 p {"{description}"}
-                p {"Cost {format_decimal_loc(loc_cost)}"}
+                p {"Cost {format_decimal_loc(loc_cost, state.read().settings.notation)}"}
+                if disabled {
+                    p {"{eta}"}
+                }
                 button {
                     disabled: disabled,
                     class: "repeatable-action-button",
                     onclick: move |_| {
                         action.call(state);
-                        state.write().logs.log(debug_message.as_str())
+                        if let Some(debug_message) = &debug_message {
+                            state.write().logs.log(LogCategory::Action, debug_message);
+                        }
                 }
                 , {button_name} }
             }
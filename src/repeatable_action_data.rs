@@ -1,19 +1,26 @@
 #![allow(non_snake_case)]
-use crate::constants::{GameConstants, Research};
+use crate::actions::Action;
+use crate::confirmation::click_action;
+use crate::constants::{use_game_constants, Research};
+use crate::git::Commits;
+use crate::hr_quotas::HrQuotaSliders;
+use crate::layoffs::{LayoffPlan, LayoffPolicyPanel};
+use crate::producers::Producer;
 use crate::repeatable_action::RepeatableAction;
-use break_infinity::{sum_geometric_series, Decimal};
+use break_infinity::Decimal;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
 use dioxus::prelude::{Signal, Writable};
 
-use crate::state::State;
+use crate::state::{use_game_state, State};
 
 #[component]
-pub(crate) fn RepeatableActions(mut state: Signal<State>, constants: GameConstants) -> Element {
+pub(crate) fn RepeatableActions() -> Element {
+    let state = use_game_state();
+    let constants = use_game_constants();
     rsx! {
         RepeatableAction{
-            state: state,
             require: Some(Research::Internship),
             produced: Some(state.read().manual_interns),
             button_name: "hire intern",
@@ -21,19 +28,14 @@ pub(crate) fn RepeatableActions(mut state: Signal<State>, constants: GameConstan
             description: "Produces loc, and bugs",
             loc_base_cost: constants.interns_loc_base_cost,
             loc_growth_rate: constants.interns_loc_growth_rate,
-            action: move |mut s: Signal<State>| {
-                s.write().manual_interns += Decimal::ONE;
-                let manual_interns_loc_cost = sum_geometric_series(
-                    &Decimal::ONE,
-                    &constants.interns_loc_base_cost,
-                    &constants.interns_loc_growth_rate,
-                    &state.read().manual_interns,
-                );
-                s.write().loc -= manual_interns_loc_cost;
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    Action::Hire(Producer::Interns).apply(&mut s.write(), &constants);
+                }
             },
         }
         RepeatableAction{
-            state: state,
             require: Some(Research::JuniorDevsPosition),
             produced: Some(state.read().manual_junior_devs),
             button_name: "hire junior devs",
@@ -41,19 +43,14 @@ pub(crate) fn RepeatableActions(mut state: Signal<State>, constants: GameConstan
             description: "Produces loc, and bugs",
             loc_base_cost: constants.junior_devs_loc_base_cost,
             loc_growth_rate: constants.junior_devs_loc_growth_rate,
-            action: move |mut s: Signal<State>| {
-                s.write().manual_junior_devs += Decimal::ONE;
-                let manual_junior_devs_loc_cost = sum_geometric_series(
-                    &Decimal::ONE,
-                    &constants.junior_devs_loc_base_cost,
-                    &constants.junior_devs_loc_growth_rate,
-                    &state.read().manual_junior_devs,
-                );
-                s.write().loc -= manual_junior_devs_loc_cost;
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    Action::Hire(Producer::JuniorDevs).apply(&mut s.write(), &constants);
+                }
             },
         }
         RepeatableAction{
-            state: state,
             require: Some(Research::SeniorDevsPosition),
             produced: Some(state.read().manual_senior_devs),
             button_name: "hire senior devs",
@@ -61,19 +58,14 @@ pub(crate) fn RepeatableActions(mut state: Signal<State>, constants: GameConstan
             description: "Produces loc, and bugs",
             loc_base_cost: constants.senior_devs_loc_base_cost,
             loc_growth_rate: constants.senior_devs_loc_growth_rate,
-            action: move |mut s: Signal<State>| {
-                s.write().manual_senior_devs += Decimal::ONE;
-                let manual_senior_devs_loc_cost = sum_geometric_series(
-                    &Decimal::ONE,
-                    &constants.senior_devs_loc_base_cost,
-                    &constants.senior_devs_loc_growth_rate,
-                    &state.read().manual_senior_devs,
-                );
-                s.write().loc -= manual_senior_devs_loc_cost;
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    Action::Hire(Producer::SeniorDevs).apply(&mut s.write(), &constants);
+                }
             },
         }
         RepeatableAction{
-            state: state,
             require: Some(Research::HumanResources),
             produced: Some(state.read().manual_hrs),
             button_name: "hire HR",
@@ -81,19 +73,15 @@ pub(crate) fn RepeatableActions(mut state: Signal<State>, constants: GameConstan
             description: "Hire devs",
             loc_base_cost: constants.hrs_loc_base_cost,
             loc_growth_rate: constants.hrs_loc_growth_rate,
-            action: move |mut s: Signal<State>| {
-                s.write().manual_hrs += Decimal::ONE;
-                let manual_hrs_loc_cost = sum_geometric_series(
-                    &Decimal::ONE,
-                    &constants.hrs_loc_base_cost,
-                    &constants.hrs_loc_growth_rate,
-                    &state.read().manual_hrs,
-                );
-                s.write().loc -= manual_hrs_loc_cost;
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    Action::Hire(Producer::Hrs).apply(&mut s.write(), &constants);
+                }
             },
         }
+        HrQuotaSliders {}
         RepeatableAction{
-            state: state,
             require: Some(Research::ProjectManagement),
             produced: Some(state.read().manual_pms),
             button_name: "hire PM",
@@ -101,49 +89,58 @@ pub(crate) fn RepeatableActions(mut state: Signal<State>, constants: GameConstan
             description: "Convert bugs to features",
             loc_base_cost: constants.pms_loc_base_cost,
             loc_growth_rate: constants.pms_loc_growth_rate,
-            action: move |mut s: Signal<State>| {
-                s.write().manual_pms += Decimal::ONE;
-                let manual_pms_loc_cost = sum_geometric_series(
-                    &Decimal::ONE,
-                    &constants.pms_loc_base_cost,
-                    &constants.pms_loc_growth_rate,
-                    &state.read().manual_pms,
-                );
-                s.write().loc -= manual_pms_loc_cost;
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    Action::Hire(Producer::Pms).apply(&mut s.write(), &constants);
+                }
             },
         }
         RepeatableAction{
-            state: state,
             require: Some(Research::Rmrf),
             produced: None,
             button_name: "rm -rf",
-            debug_message: "rm -rf",
-            description: "Wipe all loc and bugs",
+            description: "Wipe all loc and bugs, git commits are kept",
             loc_base_cost: Decimal::ZERO,
             loc_growth_rate: Decimal::ONE,
-            action: move |mut s: Signal<State>| {
-                s.write().loc = Decimal::ZERO;
-                s.write().bugs = Decimal::ZERO;
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    click_action(&mut s.write(), &constants, Action::Rmrf);
+                }
             },
         }
         RepeatableAction{
-            state: state,
+            require: Some(Research::GitCommit),
+            produced: None,
+            button_name: "git commit",
+            debug_message: "git commit",
+            description: "Save a checkpoint of loc, bugs and features",
+            loc_base_cost: Decimal::ZERO,
+            loc_growth_rate: Decimal::ONE,
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    Action::GitCommit.apply(&mut s.write(), &constants);
+                }
+            },
+        }
+        Commits {}
+        RepeatableAction{
             require: Some(Research::SmartStaffing),
             produced: None,
             button_name: "Smart Staffing",
-            debug_message: "smart staffing",
-            description: "Optimize head count by 20%",
-            loc_base_cost: Decimal::ZERO,
+            description: "Optimize head count, following the layoff policy",
+            // the severance of the current policy
+            loc_base_cost: LayoffPlan::new(&state.read(), &constants).severance,
             loc_growth_rate: Decimal::ONE,
-            action: move |mut s: Signal<State>| {
-                let ratio = Decimal::new(0.8);
-                s.write().interns *= ratio;
-                s.write().manual_interns *= ratio;
-                s.write().junior_devs *= ratio;
-                s.write().manual_junior_devs *= ratio;
-                s.write().senior_devs *= ratio;
-                s.write().manual_senior_devs *= ratio;
+            action: {
+                let constants = constants.clone();
+                move |mut s: Signal<State>| {
+                    click_action(&mut s.write(), &constants, Action::SmartStaffing);
+                }
             },
         }
+        LayoffPolicyPanel {}
     }
 }
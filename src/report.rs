@@ -0,0 +1,81 @@
+#![allow(non_snake_case)]
+use crate::constants::{use_game_constants, GameConstants};
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use dioxus::prelude::Writable;
+use serde::Serialize;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+/**
+ * Everything needed to investigate a bug report, the log history kept by the log size setting
+ * is part of `state`
+ */
+#[derive(Serialize)]
+struct Report<'a> {
+    version: &'static str,
+    created_at: u64,
+    // wall time since the page was loaded, in seconds
+    session_duration: f64,
+    constants: &'a GameConstants,
+    state: &'a State,
+}
+
+pub(crate) fn report_json(state: &State, constants: &GameConstants) -> serde_json::Result<String> {
+    let report = Report {
+        version: env!("CARGO_PKG_VERSION"),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs()),
+        session_duration: state.session_start.elapsed().as_secs_f64(),
+        constants,
+        state,
+    };
+    serde_json::to_string_pretty(&report)
+}
+
+#[component]
+pub(crate) fn ReportAction() -> Element {
+    let mut state = use_game_state();
+    let constants = use_game_constants();
+    let log_size = state.read().settings.log_size;
+    rsx! {
+        button {
+            class: "repeatable-action-button",
+            title: "state, constants and the last {log_size} log entries, as kept by the log size setting",
+            onclick: move |_| {
+                let report = report_json(&state.read(), &constants);
+                let json = match report {
+                    Ok(json) => json,
+                    Err(error) => {
+                        state.write().logs.log(
+                            LogCategory::System,
+                            &format!("failed to generate report: {}", error)
+                        );
+                        return;
+                    }
+                };
+                let file_name = format!("reasonably-report-{}.json", state.read().rng_seed);
+                state.write().logs.log(LogCategory::System, &format!("downloading {}", file_name));
+                let download = eval(r#"
+                const [fileName, json] = await dioxus.recv();
+                const url = URL.createObjectURL(new Blob([json], { type: "application/json" }));
+                const link = document.createElement("a");
+                link.href = url;
+                link.download = fileName;
+                link.click();
+                URL.revokeObjectURL(url);
+                "#);
+                if let Err(error) = download.send(serde_json::json!([file_name, json])) {
+                    state.write().logs.log(
+                        LogCategory::System,
+                        &format!("failed to download report: {:?}", error)
+                    );
+                }
+            },
+            "download report"
+        }
+    }
+}
@@ -1,171 +1,239 @@
 #![allow(non_snake_case)]
-use crate::constants::{GameConstants, Research};
+use crate::constants::{use_game_constants, GameConstants, Research};
+use crate::modifiers::{Modifier, ModifierSource, Operation, Stat};
 use break_infinity::Decimal;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
-use dioxus::prelude::{Signal, Writable};
 
+use crate::quest_data::quest_definitions;
 use crate::research_once::ResearchOnce;
 use crate::state::State;
 
+/**
+ * Everything needed to display and buy a research (or a quest) once
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ResearchDefinition {
+    pub research: Research,
+    pub require: Option<Research>,
+    pub button_name: &'static str,
+    pub description: &'static str,
+    pub loc_cost: Decimal,
+}
+
+pub(crate) fn research_definitions(constants: &GameConstants) -> Vec<ResearchDefinition> {
+    vec![
+        ResearchDefinition {
+            research: Research::ToggleTheme,
+            require: None,
+            button_name: "Install theme",
+            description: "allow toggling and picking themes",
+            loc_cost: constants.research_toggle_theme_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::Internship,
+            require: None,
+            button_name: "Research internship",
+            description: "allow hiring interns, who produce loc and bugs automatically",
+            loc_cost: constants.research_internship_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::JuniorDevsPosition,
+            require: Some(Research::Internship),
+            button_name: "Research junior devs",
+            description: "allow hiring junior devs, who produce loc and bugs automatically",
+            loc_cost: constants.research_junior_devs_position_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::SeniorDevsPosition,
+            require: Some(Research::JuniorDevsPosition),
+            button_name: "research senior devs",
+            description: "Allow hiring senior devs, who produce loc and bugs automatically",
+            loc_cost: constants.research_senior_devs_position_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::CodeMetrics,
+            require: None,
+            button_name: "research code metrics",
+            description: "Display LOC/s and bugs/s",
+            loc_cost: constants.research_code_metrics_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::Speedrun,
+            require: None,
+            button_name: "research speedrun",
+            description: "Display progress bar and real time timer",
+            loc_cost: constants.research_speedrun_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::Logs,
+            require: None,
+            button_name: "research logs",
+            description: "Display logs",
+            loc_cost: constants.research_logs_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::Rmrf,
+            require: None,
+            button_name: "learn rm -rf",
+            description: "For desperate situations, allow using rm -rf command",
+            loc_cost: constants.research_rmrf_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::GitCommit,
+            require: Some(Research::Rmrf),
+            button_name: "learn git commit",
+            description: "Save checkpoints of loc, bugs and features, rm -rf keeps them",
+            loc_cost: constants.research_git_commit_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::GitRevert,
+            require: Some(Research::GitCommit),
+            button_name: "learn git revert",
+            description: "Restore a checkpoint, minus the loc spent since, a share of its loc and the features lost since",
+            loc_cost: constants.research_git_revert_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::InternsPromotion,
+            require: Some(Research::JuniorDevsPosition),
+            button_name: "promote interns",
+            description: "Allow interns to be promoted to junior devs",
+            loc_cost: constants.research_interns_promotion_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::JuniorDevsPromotion,
+            require: Some(Research::SeniorDevsPosition),
+            button_name: "promote junior devs",
+            description: "Allow junior devs to be promoted to senior devs",
+            loc_cost: constants.research_junior_devs_promotion_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::SyntaxColoringMultiplier,
+            require: Some(Research::Internship),
+            button_name: "install syntax coloring",
+            description: "Boost interns locs/s x2",
+            loc_cost: constants.research_syntax_coloring_multiplier_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::HumanResources,
+            require: Some(Research::SeniorDevsPosition),
+            button_name: "research human resources",
+            description: "Allow hiring HR, who hire devs",
+            loc_cost: constants.research_human_resources_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::ProjectManagement,
+            require: Some(Research::SeniorDevsPosition),
+            button_name: "research project management",
+            description: "Allow hiring PM, who convert bugs to features",
+            loc_cost: constants.research_project_management_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::SmartStaffing,
+            require: Some(Research::HumanResources),
+            button_name: "research smart staffing",
+            description: "Smart staffing a.k.a massive layoffs",
+            loc_cost: constants.research_smart_staffing_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::RecursiveHR,
+            require: Some(Research::HumanResources),
+            button_name: "research recursive HR",
+            description: "HR now recruit themselves",
+            loc_cost: constants.research_recursive_hr_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::HiringQuotas,
+            require: Some(Research::HumanResources),
+            button_name: "research hiring quotas",
+            description: "Choose how HR split their hiring between roles, PMs included",
+            loc_cost: constants.research_hiring_quotas_loc_cost,
+        },
+        ResearchDefinition {
+            research: Research::ManagementCareer,
+            require: Some(Research::ProjectManagement),
+            button_name: "research management career",
+            description: "Instead of retiring, some senior devs will become PMs",
+            loc_cost: constants.research_management_career_loc_cost,
+        },
+    ]
+}
+
+/**
+ * Definition of a research or of a quest, `None` for the ones that cannot be bought
+ */
+pub(crate) fn research_definition(
+    research: &Research,
+    constants: &GameConstants,
+) -> Option<ResearchDefinition> {
+    research_definitions(constants)
+        .into_iter()
+        .chain(quest_definitions(constants))
+        .find(|definition| definition.research == *research)
+}
+
+/**
+ * Side effects of a research, applied once when it is researched
+ */
+pub(crate) fn apply_research_effect(
+    research: &Research,
+    state: &mut State,
+    constants: &GameConstants,
+) {
+    match research {
+        Research::SyntaxColoringMultiplier => {
+            state.modifiers.push(Modifier::new(
+                "syntax coloring",
+                Stat::InternsLocDt,
+                Operation::Multiply,
+                constants.research_syntax_coloring_multiplier,
+                ModifierSource::Research(Research::SyntaxColoringMultiplier),
+            ));
+        }
+        Research::ManagementCareer => {
+            let retirement_ratio_dt = state.senior_devs_retirement_ratio_dt;
+            state.modifiers.push(Modifier::new(
+                "management career",
+                Stat::SeniorDevsRetirementRatioDt,
+                Operation::Multiply,
+                Decimal::ONE - constants.senior_devs_management_career_ratio,
+                ModifierSource::Research(Research::ManagementCareer),
+            ));
+            state.modifiers.push(Modifier::new(
+                "management career",
+                Stat::SeniorDevsManagementRatioDt,
+                Operation::Add,
+                retirement_ratio_dt * constants.senior_devs_management_career_ratio,
+                ModifierSource::Research(Research::ManagementCareer),
+            ));
+        }
+        _ => {}
+    }
+}
+
 #[component]
-pub(crate) fn Researches(mut state: Signal<State>, constants: GameConstants) -> Element {
+pub(crate) fn Researches() -> Element {
+    let constants = use_game_constants();
+    let researches = research_definitions(&constants)
+        .into_iter()
+        .map(|definition| {
+            rsx! {
+                ResearchOnce{
+                    key: "{definition.research:?}",
+                    require: definition.require,
+                    research_name: definition.research,
+                    button_name: definition.button_name,
+                    description: definition.description,
+                    loc_cost: definition.loc_cost,
+                    quest: false,
+                }
+            }
+        });
     rsx! {
         div { // vertical
             class: "researches",
-            ResearchOnce{
-                state: state,
-                require: None,
-                research_name: Research::ToggleTheme,
-                button_name: "Install theme",
-                description: "allow toggling theme",
-                loc_cost: constants.research_toggle_theme_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: None,
-                research_name: Research::Internship,
-                button_name: "Research internship",
-                description: "allow hiring interns, who produce loc and bugs automatically",
-                loc_cost: constants.research_internship_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::Internship),
-                research_name: Research::JuniorDevsPosition,
-                button_name: "Research junior devs",
-                description: "allow hiring junior devs, who produce loc and bugs automatically",
-                loc_cost: constants.research_junior_devs_position_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::JuniorDevsPosition),
-                research_name: Research::SeniorDevsPosition,
-                button_name: "research senior devs",
-                description: "Allow hiring senior devs, who produce loc and bugs automatically",
-                loc_cost: constants.research_senior_devs_position_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: None,
-                research_name: Research::CodeMetrics,
-                button_name: "research code metrics",
-                description: "Display LOC/s and bugs/s",
-                loc_cost: constants.research_code_metrics_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: None,
-                research_name: Research::Speedrun,
-                button_name: "research speedrun",
-                description: "Display progress bar and real time timer",
-                loc_cost: constants.research_speedrun_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: None,
-                research_name: Research::Logs,
-                button_name: "research logs",
-                description: "Display logs",
-                loc_cost: constants.research_logs_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: None,
-                research_name: Research::Rmrf,
-                button_name: "learn rm -rf",
-                description: "For desperate situations, allow using rm -rf command",
-                loc_cost: constants.research_rmrf_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::JuniorDevsPosition),
-                research_name: Research::InternsPromotion,
-                button_name: "promote interns",
-                description: "Allow interns to be promoted to junior devs",
-                loc_cost: constants.research_interns_promotion_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::SeniorDevsPosition),
-                research_name: Research::JuniorDevsPromotion,
-                button_name: "promote junior devs",
-                description: "Allow junior devs to be promoted to senior devs",
-                loc_cost: constants.research_junior_devs_promotion_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::Internship),
-                research_name: Research::SyntaxColoringMultiplier,
-                button_name: "install syntax coloring",
-                description: "Boost interns locs/s x2",
-                loc_cost: constants.research_syntax_coloring_multiplier_loc_cost,
-                quest: false,
-                action: move |mut s: Signal<State>| {
-                    s.write().interns_loc_dt *= constants.research_syntax_coloring_multiplier;
-                },
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::SeniorDevsPosition),
-                research_name: Research::HumanResources,
-                button_name: "research human resources",
-                description: "Allow hiring HR, who hire devs",
-                loc_cost: constants.research_human_resources_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::SeniorDevsPosition),
-                research_name: Research::ProjectManagement,
-                button_name: "research project management",
-                description: "Allow hiring PM, who convert bugs to features",
-                loc_cost: constants.research_project_management_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::HumanResources),
-                research_name: Research::SmartStaffing,
-                button_name: "research smart staffing",
-                description: "Smart staffing a.k.a massive layoffs",
-                loc_cost: constants.research_smart_staffing_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::HumanResources),
-                research_name: Research::RecursiveHR,
-                button_name: "research recursive HR",
-                description: "HR now recruit themselves",
-                loc_cost: constants.research_recursive_hr_loc_cost,
-                quest: false,
-            }
-            ResearchOnce{
-                state: state,
-                require: Some(Research::ProjectManagement),
-                research_name: Research::ManagementCareer,
-                button_name: "research management career",
-                description: "Instead of retiring, some senior devs will become PMs",
-                loc_cost: constants.research_management_career_loc_cost,
-                quest: false,
-                action: move |mut s: Signal<State>| {
-                    let retirement_ratio_dt = s.read().senior_devs_retirement_ratio_dt;
-                    s.write().senior_devs_retirement_ratio_dt = retirement_ratio_dt * (Decimal::ONE - constants.senior_devs_management_career_ratio);
-                    s.write().senior_devs_management_ratio_dt = retirement_ratio_dt * constants.senior_devs_management_career_ratio;
-                },
-            }
+            {researches}
         }
     }
 }
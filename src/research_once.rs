@@ -1,17 +1,19 @@
 #![allow(non_snake_case)]
-use crate::constants::Research;
+use crate::actions::Action;
+use crate::constants::{use_game_constants, Research};
 use break_infinity::Decimal;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
 use dioxus::prelude::{Signal, Writable};
 
+use crate::eta::{format_eta, seconds_until_affordable};
 use crate::format_decimal::format_decimal_loc;
-use crate::state::State;
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
 
 #[component]
 pub(crate) fn ResearchOnce(
-    mut state: Signal<State>,
     research_name: Research,
     require: Option<Research>,
     button_name: String,
@@ -21,13 +23,16 @@ pub(crate) fn ResearchOnce(
     quest: bool,
     action: Option<EventHandler<Signal<State>>>,
 ) -> Element {
-    let debug_message = debug_message.unwrap_or_else(|| format!("{:?} researched", Research::ToggleTheme));
-    let (css_class, css_button_class) = if quest {
-        ("quest", "quest-button")
+    let mut state = use_game_state();
+    let constants = use_game_constants();
+    let debug_message = debug_message.unwrap_or_else(|| format!("{:?} researched", research_name));
+    let (css_class, css_button_class, log_category) = if quest {
+        ("quest", "quest-button", LogCategory::Quest)
     } else {
-        ("research", "research-button")
+        ("research", "research-button", LogCategory::Research)
     };
     let disabled = state.read().loc < loc_cost;
+    let eta = format_eta(seconds_until_affordable(loc_cost, &state.read()));
     let already_researched = state.read().researched.contains(&research_name);
     let requirements_met = require.map_or_else(
         || true,
@@ -38,16 +43,22 @@ pub(crate) fn ResearchOnce(
             div {
                 class: css_class,
                 p {"{description}"}
-                p {"Cost {format_decimal_loc(loc_cost)}"}
+                p {"Cost {format_decimal_loc(loc_cost, state.read().settings.notation)}"}
+                if disabled {
+                    p {"{eta}"}
+                }
                 button {
                     class: css_button_class,
                     disabled: disabled,
                     onclick: move |_| {
-                        state.write().researched.insert(research_name.clone());
+                        // costs and research effects are handled by the action
+                        if !Action::Research(research_name).apply(&mut state.write(), &constants) {
+                            return;
+                        }
                         state.write().logs.log(
+                            log_category,
                             &debug_message
                         );
-                        state.write().loc -= loc_cost;
                         if let Some(action) = action {
                             action.call(state);
                         }
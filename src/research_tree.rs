@@ -0,0 +1,237 @@
+#![allow(non_snake_case)]
+use crate::constants::{use_game_constants, Research};
+use crate::format_decimal::format_decimal_loc;
+use crate::quest_data::quest_definitions;
+use crate::research_data::{research_definitions, ResearchDefinition};
+use crate::state::{use_game_state, State};
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+
+// tree layout, in SVG user units
+const NODE_WIDTH: usize = 220;
+const NODE_HEIGHT: usize = 24;
+const ROW_HEIGHT: usize = 32;
+const INDENT: usize = 24;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum ResearchStatus {
+    Locked,
+    Available,
+    Researched,
+}
+
+impl ResearchStatus {
+    pub(crate) fn of(definition: &ResearchDefinition, state: &State) -> Self {
+        if state.researched.contains(&definition.research) {
+            ResearchStatus::Researched
+        } else if definition
+            .require
+            .as_ref()
+            .is_none_or(|require| state.researched.contains(require))
+        {
+            ResearchStatus::Available
+        } else {
+            ResearchStatus::Locked
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ResearchStatus::Locked => "locked",
+            ResearchStatus::Available => "available",
+            ResearchStatus::Researched => "researched",
+        }
+    }
+}
+
+/**
+ * Position of a definition in the graph, `parent` indexes the node of its prerequisite
+ */
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct TreeNode {
+    pub depth: usize,
+    pub row: usize,
+    pub parent: Option<usize>,
+}
+
+/**
+ * Definitions in depth first order, each one below its prerequisite and indented by its depth
+ */
+pub(crate) fn research_tree(
+    definitions: &[ResearchDefinition],
+) -> Vec<(TreeNode, &ResearchDefinition)> {
+    fn visit<'a>(
+        definitions: &'a [ResearchDefinition],
+        parent: Option<(usize, &Research)>,
+        depth: usize,
+        tree: &mut Vec<(TreeNode, &'a ResearchDefinition)>,
+    ) {
+        for definition in definitions.iter().filter(|definition| {
+            definition.require.as_ref() == parent.map(|(_, research)| research)
+        }) {
+            let index = tree.len();
+            let node = TreeNode {
+                depth,
+                row: index,
+                parent: parent.map(|(index, _)| index),
+            };
+            tree.push((node, definition));
+            visit(
+                definitions,
+                Some((index, &definition.research)),
+                depth + 1,
+                tree,
+            );
+        }
+    }
+    let mut tree = Vec::new();
+    visit(definitions, None, 0, &mut tree);
+    tree
+}
+
+#[component]
+fn ResearchGraph(title: String, definitions: Vec<ResearchDefinition>) -> Element {
+    let state = use_game_state();
+    let mut hovered: Signal<Option<usize>> = use_signal(|| None);
+    let notation = state.read().settings.notation;
+    let tree = research_tree(&definitions);
+    let max_depth = tree.iter().map(|(node, _)| node.depth).max().unwrap_or(0);
+    let width = max_depth * INDENT + NODE_WIDTH + 2;
+    let height = tree.len() * ROW_HEIGHT;
+    let edges = tree.iter().filter_map(|(node, _)| {
+        let (parent, _) = tree[node.parent?];
+        // elbow from below the prerequisite to the left of the node
+        let path = format!(
+            "M {} {} V {} H {}",
+            parent.depth * INDENT + INDENT / 2,
+            parent.row * ROW_HEIGHT + NODE_HEIGHT,
+            node.row * ROW_HEIGHT + NODE_HEIGHT / 2,
+            node.depth * INDENT
+        );
+        rsx! {
+            path {
+                class: "research-edge",
+                d: path,
+            }
+        }
+    });
+    let nodes = tree.iter().enumerate().map(|(index, (node, definition))| {
+        let status = ResearchStatus::of(definition, &state.read());
+        let x = node.depth * INDENT;
+        let y = node.row * ROW_HEIGHT;
+        rsx! {
+            g {
+                key: "{definition.research:?}",
+                class: "research-node {status.name()}",
+                onmouseenter: move |_| hovered.set(Some(index)),
+                onmouseleave: move |_| hovered.set(None),
+                rect {
+                    x: "{x}",
+                    y: "{y}",
+                    width: "{NODE_WIDTH}",
+                    height: "{NODE_HEIGHT}",
+                    rx: "4",
+                }
+                text {
+                    x: "{x + 8}",
+                    y: "{y + NODE_HEIGHT / 2}",
+                    dominant_baseline: "middle",
+                    "{definition.button_name}"
+                }
+            }
+        }
+    });
+    let details = hovered()
+        .and_then(|index| tree.get(index))
+        .map(|(_, definition)| {
+            let require = definitions
+                .iter()
+                .find(|required| Some(&required.research) == definition.require.as_ref())
+                .map(|required| required.button_name);
+            (
+                definition.button_name,
+                format_decimal_loc(definition.loc_cost, notation),
+                definition.description,
+                ResearchStatus::of(definition, &state.read()).name(),
+                require,
+            )
+        });
+    rsx! {
+        div {
+            class: "research-graph",
+            h3 {"{title}"}
+            div {
+                class: "research-graph-body",
+                svg {
+                    width: "{width}",
+                    height: "{height}",
+                    view_box: "0 0 {width} {height}",
+                    {edges}
+                    {nodes}
+                }
+                div {
+                    class: "research-details",
+                    if let Some((name, cost, description, status, require)) = details {
+                        h4 {"{name}"}
+                        p {"Cost {cost}"}
+                        p {"{description}"}
+                        if let Some(require) = require {
+                            p {"Requires {require}"}
+                        }
+                        p {"{status}"}
+                    } else {
+                        p {"Hover a node for its cost and effect."}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub(crate) fn ResearchTree() -> Element {
+    let constants = use_game_constants();
+    rsx! {
+        div {
+            class: "research-tree",
+            h2 {"research tree"}
+            ResearchGraph {
+                title: "researches",
+                definitions: research_definitions(&constants),
+            }
+            ResearchGraph {
+                title: "quests",
+                definitions: quest_definitions(&constants),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::GameConstants;
+
+    #[test]
+    fn every_research_is_placed_after_its_prerequisite() {
+        let definitions = research_definitions(&GameConstants::default());
+        let tree = research_tree(&definitions);
+        assert_eq!(tree.len(), definitions.len());
+        for (row, (node, definition)) in tree.iter().enumerate() {
+            assert_eq!(node.row, row);
+            match node.parent {
+                Some(parent) => {
+                    let (parent_node, parent_definition) = tree[parent];
+                    assert!(parent < row);
+                    assert_eq!(parent_node.depth + 1, node.depth);
+                    assert_eq!(
+                        definition.require.as_ref(),
+                        Some(&parent_definition.research)
+                    );
+                }
+                None => assert_eq!(node.depth, 0),
+            }
+        }
+    }
+}
@@ -1,17 +1,215 @@
 #![allow(non_snake_case)]
+use crate::constants::{use_game_constants, GameConstants};
+use crate::eta::seconds_until_affordable;
 use crate::format_decimal::{
-    format_decimal_bugs, format_decimal_devs, format_decimal_features, format_decimal_hrs,
-    format_decimal_loc, format_decimal_pms,
+    format_decimal_bugs, format_decimal_devs, format_decimal_features, format_decimal_loc, Notation,
 };
-use crate::state::State;
+use crate::format_duration::format_duration;
+use crate::producers::Producer;
+use crate::rates::Rates;
+use crate::state::{use_game_state, State};
 use break_infinity::Decimal;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::Signal;
 use dioxus::prelude::*;
+use std::cmp::Ordering;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Column {
+    Role,
+    Headcount,
+    Production,
+    LocShare,
+    Bugs,
+    NextCost,
+    Eta,
+}
+
+impl Column {
+    const ALL: [Column; 7] = [
+        Column::Role,
+        Column::Headcount,
+        Column::Production,
+        Column::LocShare,
+        Column::Bugs,
+        Column::NextCost,
+        Column::Eta,
+    ];
+
+    fn title(&self) -> &'static str {
+        match self {
+            Column::Role => "staff",
+            Column::Headcount => "count",
+            Column::Production => "production",
+            Column::LocShare => "loc share",
+            Column::Bugs => "bugs/s",
+            Column::NextCost => "next hire",
+            Column::Eta => "affordable in",
+        }
+    }
+}
+
+struct ProducerRow {
+    producer: Producer,
+    name: String,
+    headcount: Decimal,
+    // per second
+    production: Decimal,
+    format_production: fn(Decimal, Notation) -> String,
+    loc_share: f64,
+    bugs: Decimal,
+    // only for rows that can be hired manually
+    next_loc_cost: Option<Decimal>,
+    eta: Option<f64>,
+}
+
+impl ProducerRow {
+    fn compare(&self, other: &ProducerRow, column: Column) -> Ordering {
+        let by_decimal = |a: Decimal, b: Decimal| a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+        match column {
+            Column::Role => self.name.cmp(&other.name),
+            Column::Headcount => by_decimal(self.headcount, other.headcount),
+            Column::Production => by_decimal(self.production, other.production),
+            Column::LocShare => self.loc_share.total_cmp(&other.loc_share),
+            Column::Bugs => by_decimal(self.bugs, other.bugs),
+            Column::NextCost => by_decimal(
+                self.next_loc_cost.unwrap_or(Decimal::ZERO),
+                other.next_loc_cost.unwrap_or(Decimal::ZERO),
+            ),
+            Column::Eta => self
+                .eta
+                .unwrap_or(f64::INFINITY)
+                .total_cmp(&other.eta.unwrap_or(f64::INFINITY)),
+        }
+    }
+
+    fn cells(&self, notation: Notation) -> [String; 7] {
+        let eta = match (self.next_loc_cost, self.eta) {
+            (None, _) => "-".to_string(),
+            (Some(_), None) => "never".to_string(),
+            (Some(_), Some(seconds)) if seconds <= 0.0 => "now".to_string(),
+            (Some(_), Some(seconds)) => format_duration(seconds),
+        };
+        [
+            self.name.clone(),
+            self.producer.format_headcount(self.headcount, notation),
+            format!("{}/s", (self.format_production)(self.production, notation)),
+            format!("{:.1}%", self.loc_share * 100.0),
+            format_decimal_bugs(self.bugs, notation),
+            self.next_loc_cost.map_or_else(
+                || "-".to_string(),
+                |loc_cost| format_decimal_loc(loc_cost, notation),
+            ),
+            eta,
+        ]
+    }
+}
+
+fn producer_rows(state: &State, constants: &GameConstants, merged: bool) -> Vec<ProducerRow> {
+    let rates = Rates::new(state);
+    let dt_seconds = state.dt_seconds;
+    let mut rows = vec![];
+    for producer in Producer::ALL {
+        let auto = producer.auto(state);
+        let manual = producer.manual(state);
+        let headcount = auto + manual;
+        if headcount <= Decimal::ZERO {
+            continue;
+        }
+        let (loc, bugs, production, format_production): (_, _, _, fn(Decimal, Notation) -> String) =
+            match producer {
+                Producer::Interns => (
+                    rates.interns.loc,
+                    rates.interns.bugs,
+                    rates.interns.loc,
+                    format_decimal_loc,
+                ),
+                Producer::JuniorDevs => (
+                    rates.junior_devs.loc,
+                    rates.junior_devs.bugs,
+                    rates.junior_devs.loc,
+                    format_decimal_loc,
+                ),
+                Producer::SeniorDevs => (
+                    rates.senior_devs.loc,
+                    rates.senior_devs.bugs,
+                    rates.senior_devs.loc,
+                    format_decimal_loc,
+                ),
+                Producer::Hrs => (
+                    Decimal::ZERO,
+                    Decimal::ZERO,
+                    rates.hired_interns
+                        + rates.hired_junior_devs
+                        + rates.hired_senior_devs
+                        + rates.hired_hrs
+                        + rates.hired_pms,
+                    format_decimal_devs,
+                ),
+                Producer::Pms => (
+                    Decimal::ZERO,
+                    -rates.bugs_converted,
+                    rates.bugs_converted,
+                    format_decimal_features,
+                ),
+            };
+        let loc_share = if rates.loc > Decimal::ZERO {
+            (loc / rates.loc).to_number()
+        } else {
+            0.0
+        };
+        let next_loc_cost = producer.next_loc_cost(state, constants);
+        let eta = seconds_until_affordable(next_loc_cost, state);
+        // split the role production between auto and manual headcount
+        let mut push_row = |name: String, count: Decimal, next_loc_cost: Option<Decimal>| {
+            let share = count / headcount;
+            rows.push(ProducerRow {
+                producer,
+                name,
+                headcount: count,
+                production: production * share * dt_seconds,
+                format_production,
+                loc_share: loc_share * share.to_number(),
+                bugs: bugs * share * dt_seconds,
+                next_loc_cost,
+                eta,
+            });
+        };
+        if merged {
+            push_row(producer.name().to_string(), headcount, Some(next_loc_cost));
+        } else {
+            if auto > Decimal::ZERO {
+                push_row(format!("{} (auto)", producer.name()), auto, None);
+            }
+            if manual > Decimal::ZERO {
+                push_row(producer.name().to_string(), manual, Some(next_loc_cost));
+            }
+        }
+    }
+    rows
+}
 
 #[component]
-pub(crate) fn Resources(state: Signal<State>) -> Element {
+pub(crate) fn Resources() -> Element {
+    let state = use_game_state();
+    let constants = use_game_constants();
+    // None keeps the natural order, otherwise (column, descending)
+    let mut sort_by: Signal<Option<(Column, bool)>> = use_signal(|| None);
+    let mut merged = use_signal(|| false);
+    let mut rows = producer_rows(&state.read(), &constants, merged());
+    if let Some((column, descending)) = sort_by() {
+        rows.sort_by(|a, b| {
+            let ordering = a.compare(b, column);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+    let notation = state.read().settings.notation;
+    let rows: Vec<[String; 7]> = rows.iter().map(|row| row.cells(notation)).collect();
     rsx! {
         div {
             class: "resources",
@@ -32,7 +230,7 @@ pub(crate) fn Resources(state: Signal<State>) -> Element {
                         td {"Lines of code"}
                         td {
                             class: "table-value",
-                            "{format_decimal_loc(state.read().loc)}"
+                            "{format_decimal_loc(state.read().loc, notation)}"
                         }
                     }
                 }
@@ -41,7 +239,7 @@ pub(crate) fn Resources(state: Signal<State>) -> Element {
                         td{"Bugs"}
                         td{
                             class: "table-value",
-                            "{format_decimal_bugs(state.read().bugs)}"
+                            "{format_decimal_bugs(state.read().bugs, notation)}"
                         }
                     }
                 }
@@ -50,61 +248,7 @@ pub(crate) fn Resources(state: Signal<State>) -> Element {
                         td{"Features"}
                         td{
                             class: "table-value",
-                            "{format_decimal_features(state.read().features)}"
-                        }
-                    }
-                }
-                if state.read().interns > Decimal::ZERO {
-                    tr {
-                        td {"Interns (auto)"}
-                        td {
-                            class: "table-value",
-                            "{format_decimal_devs(state.read().interns)}"
-                        }
-                    }
-                }
-                if state.read().manual_interns > Decimal::ZERO {
-                    tr {
-                        td {"Interns"}
-                        td {
-                            class: "table-value",
-                            "{format_decimal_devs(state.read().manual_interns)}"
-                        }
-                    }
-                }
-                if state.read().junior_devs > Decimal::ZERO {
-                    tr {
-                        td {"Junior devs (auto)"}
-                        td {
-                            class: "table-value",
-                            "{format_decimal_devs(state.read().junior_devs)}"
-                        }
-                    }
-                }
-                if state.read().manual_junior_devs > Decimal::ZERO {
-                    tr {
-                        td {"Junior devs"}
-                        td {
-                            class: "table-value",
-                            "{format_decimal_devs(state.read().manual_junior_devs)}"
-                        }
-                    }
-                }
-                if state.read().senior_devs > Decimal::ZERO {
-                    tr {
-                        td{"Senior devs (auto)"}
-                        td{
-                            class: "table-value",
-                            "{format_decimal_devs(state.read().senior_devs)}"
-                        }
-                    }
-                }
-                if state.read().manual_senior_devs > Decimal::ZERO {
-                    tr {
-                        td{"Senior devs"}
-                        td{
-                            class: "table-value",
-                            "{format_decimal_devs(state.read().manual_senior_devs)}"
+                            "{format_decimal_features(state.read().features, notation)}"
                         }
                     }
                 }
@@ -113,43 +257,52 @@ pub(crate) fn Resources(state: Signal<State>) -> Element {
                         td{"Retired devs"}
                         td{
                             class: "table-value",
-                            "{format_decimal_devs(state.read().retired_devs)}"
-                        }
-                    }
-                }
-                if state.read().hrs > Decimal::ZERO {
-                    tr {
-                        td{"HRs (auto)"}
-                        td{
-                            class: "table-value",
-                            "{format_decimal_hrs(state.read().hrs)}"
+                            "{format_decimal_devs(state.read().retired_devs, notation)}"
                         }
                     }
                 }
-                if state.read().manual_hrs > Decimal::ZERO {
-                    tr {
-                        td{"HRs"}
-                        td{
-                            class: "table-value",
-                            "{format_decimal_hrs(state.read().manual_hrs)}"
-                        }
+            }
+            if !rows.is_empty() {
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: merged(),
+                        onchange: move |event: Event<FormData>| merged.set(event.checked()),
                     }
+                    "merge auto and manual staff"
                 }
-                if state.read().pms > Decimal::ZERO {
+                table {
+                    class: "resources-table",
                     tr {
-                        td{"PMs (auto)"}
-                        td{
-                            class: "table-value",
-                            "{format_decimal_pms(state.read().pms)}"
+                        for column in Column::ALL {
+                            th {
+                                class: if column == Column::Role { "table-name sortable" } else { "table-value sortable" },
+                                onclick: move |_| {
+                                    let descending = match sort_by() {
+                                        Some((sorted_column, descending)) if sorted_column == column => !descending,
+                                        _ => column != Column::Role,
+                                    };
+                                    sort_by.set(Some((column, descending)));
+                                },
+                                {
+                                    match sort_by() {
+                                        Some((sorted_column, true)) if sorted_column == column => format!("{} ▼", column.title()),
+                                        Some((sorted_column, false)) if sorted_column == column => format!("{} ▲", column.title()),
+                                        _ => column.title().to_string(),
+                                    }
+                                }
+                            }
                         }
                     }
-                }
-                if state.read().manual_pms > Decimal::ZERO {
-                    tr {
-                        td{"PMs"}
-                        td{
-                            class: "table-value",
-                            "{format_decimal_pms(state.read().manual_pms)}"
+                    for cells in rows {
+                        tr {
+                            td {"{cells[0]}"}
+                            for cell in cells.into_iter().skip(1) {
+                                td {
+                                    class: "table-value",
+                                    "{cell}"
+                                }
+                            }
                         }
                     }
                 }
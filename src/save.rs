@@ -0,0 +1,102 @@
+use crate::cheats::update_cheats_availability;
+use crate::settings::apply_reduced_motion;
+use crate::simple_logs::LogCategory;
+use crate::state::State;
+use crate::storage;
+use dioxus::prelude::{Readable, Signal, Writable};
+
+const SAVE_KEY: &str = "save";
+
+pub(crate) fn save_game(state: &State) {
+    match serde_json::to_string(state) {
+        Ok(json) => storage::store(SAVE_KEY, &json),
+        Err(error) => dioxus_logger::tracing::error!("failed to save: {}", error),
+    }
+}
+
+/**
+ * Game saved as `json`, with settings brought back within what the settings panel allows:
+ * saves may come from older builds or have been edited by hand
+ */
+fn parse_save(json: &str) -> serde_json::Result<State> {
+    let mut saved = serde_json::from_str::<State>(json)?;
+    saved.settings.clamp();
+    update_cheats_availability(&mut saved);
+    let log_size = saved.settings.log_size;
+    saved.logs.set_max_lines(log_size);
+    Ok(saved)
+}
+
+/**
+ * Replaces `state` with the saved game if there is one, starts a new game if it cannot be read
+ */
+pub(crate) async fn load_game(mut state: Signal<State>) {
+    let Some(json) = storage::load(SAVE_KEY).await else {
+        return;
+    };
+    let mut saved = match parse_save(&json) {
+        Ok(saved) => saved,
+        Err(error) => {
+            state.write().logs.log(
+                LogCategory::System,
+                &format!("could not load save, starting a new game: {}", error),
+            );
+            return;
+        }
+    };
+    // the theme is restored on its own, from local storage or the browser preference
+    saved.theme = state.peek().theme;
+    saved.logs.log(LogCategory::System, "save loaded");
+    apply_reduced_motion(saved.settings.reduced_motion);
+    state.set(saved);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{GameConstants, Research};
+    use crate::format_decimal::Notation;
+    use crate::settings::Settings;
+    use break_infinity::Decimal;
+    use web_time::Instant;
+
+    #[test]
+    fn saved_state_round_trips() {
+        let mut state = State::new(GameConstants::default());
+        state.loc = Decimal::new(1.5e42);
+        state.researched.insert(Research::Internship);
+        state.settings.notation = Notation::Letters;
+        state.speedrun_start = Some(Instant::now());
+        state.update(Decimal::new(10.0));
+
+        let json = serde_json::to_string(&state).expect("state serializes");
+        let loaded: State = serde_json::from_str(&json).expect("state deserializes");
+
+        assert_eq!(loaded.loc, state.loc);
+        assert_eq!(loaded.researched, state.researched);
+        assert_eq!(loaded.settings, state.settings);
+        assert_eq!(loaded.game_time, state.game_time);
+        assert_eq!(loaded.rng, state.rng);
+        assert_eq!(loaded.logs.entries().count(), state.logs.entries().count());
+        assert!(loaded.speedrun_start.is_some());
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_a_new_game() {
+        let loaded: State = serde_json::from_str(r#"{"game_time": 42.0}"#).expect("partial save");
+        assert_eq!(loaded.game_time, 42.0);
+        assert_eq!(loaded.loc, Decimal::ZERO);
+    }
+
+    #[test]
+    fn saved_tick_rate_is_clamped() {
+        let mut state = State::new(GameConstants::default());
+        state.settings.tick_milliseconds = 0;
+        let json = serde_json::to_string(&state).expect("state serializes");
+        let loaded = parse_save(&json).expect("state deserializes");
+        assert_eq!(
+            loaded.settings.tick_milliseconds,
+            Settings::MIN_TICK_MILLISECONDS
+        );
+    }
+}
@@ -0,0 +1,186 @@
+#![allow(non_snake_case)]
+use crate::format_decimal::{Notation, NotationSelect};
+use crate::keybindings::{Keybindings, KeybindingsPanel};
+use crate::report::ReportAction;
+use crate::save::save_game;
+use crate::simple_logs::{LogCategory, DEFAULT_MAX_LINES};
+use crate::state::use_game_state;
+use crate::themes::ThemePicker;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use dioxus::prelude::Writable;
+use serde::{Deserialize, Serialize};
+
+/**
+ * Player preferences, part of `State` so that they are saved with the game
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Settings {
+    // real time between 2 simulation updates
+    pub tick_milliseconds: u64,
+    // minimum real time between 2 renders, updates in between are not displayed
+    pub render_milliseconds: u64,
+    pub notation: Notation,
+    pub log_size: usize,
+    // 0 disables autosave
+    pub autosave_seconds: u64,
+    // ask before rm -rf, Smart Staffing...
+    pub confirm_destructive_actions: bool,
+    pub reduced_motion: bool,
+    pub keybindings: Keybindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            tick_milliseconds: 100,
+            render_milliseconds: 100,
+            notation: Notation::default(),
+            log_size: DEFAULT_MAX_LINES,
+            autosave_seconds: 30,
+            confirm_destructive_actions: true,
+            reduced_motion: false,
+            keybindings: Keybindings::default(),
+        }
+    }
+}
+
+impl Settings {
+    // faster ticks do not fit in a browser frame, slower ones make the game sluggish
+    pub(crate) const MIN_TICK_MILLISECONDS: u64 = 20;
+    pub(crate) const MAX_TICK_MILLISECONDS: u64 = 1000;
+
+    /// keeps the tick rate within the range of its setting, the game loop divides by it
+    pub(crate) fn clamp(&mut self) {
+        self.tick_milliseconds = self
+            .tick_milliseconds
+            .clamp(Self::MIN_TICK_MILLISECONDS, Self::MAX_TICK_MILLISECONDS);
+    }
+}
+
+/**
+ * Disables the fade in animations, on top of the browser `prefers-reduced-motion`
+ */
+pub(crate) fn apply_reduced_motion(reduced_motion: bool) {
+    if let Err(error) = eval(
+        r#"
+        const reducedMotion = await dioxus.recv();
+        document.documentElement.setAttribute('data-reduced-motion', reducedMotion);
+        "#,
+    )
+    .send(reduced_motion.into())
+    {
+        dioxus_logger::tracing::error!("failed to apply reduced motion: {:?}", error);
+    }
+}
+
+#[component]
+fn NumberSetting(
+    label: String,
+    value: u64,
+    min: u64,
+    max: u64,
+    onchange: EventHandler<u64>,
+) -> Element {
+    rsx! {
+        tr {
+            td {"{label}"}
+            td {
+                input {
+                    r#type: "number",
+                    min: min as i64,
+                    max: max as i64,
+                    value: "{value}",
+                    onchange: move |event: Event<FormData>| {
+                        if let Ok(value) = event.value().parse::<u64>() {
+                            onchange.call(value.clamp(min, max));
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub(crate) fn SettingsPanel() -> Element {
+    let mut state = use_game_state();
+    let settings = state.read().settings.clone();
+    rsx! {
+        div {
+            class: "settings",
+            h2 {"settings"}
+            table {
+                NumberSetting {
+                    label: "tick (ms)",
+                    value: settings.tick_milliseconds,
+                    min: Settings::MIN_TICK_MILLISECONDS,
+                    max: Settings::MAX_TICK_MILLISECONDS,
+                    onchange: move |value| state.write().settings.tick_milliseconds = value,
+                }
+                NumberSetting {
+                    label: "render (ms)",
+                    value: settings.render_milliseconds,
+                    min: Settings::MIN_TICK_MILLISECONDS,
+                    max: 10 * Settings::MAX_TICK_MILLISECONDS,
+                    onchange: move |value| state.write().settings.render_milliseconds = value,
+                }
+                NumberSetting {
+                    label: "log size",
+                    value: settings.log_size as u64,
+                    min: 1,
+                    max: 10_000,
+                    onchange: move |value| {
+                        state.write().settings.log_size = value as usize;
+                        state.write().logs.set_max_lines(value as usize);
+                    },
+                }
+                NumberSetting {
+                    label: "autosave (s, 0 = off)",
+                    value: settings.autosave_seconds,
+                    min: 0,
+                    max: 3600,
+                    onchange: move |value| state.write().settings.autosave_seconds = value,
+                }
+                tr {
+                    td {"confirm destructive actions"}
+                    td {
+                        input {
+                            r#type: "checkbox",
+                            checked: settings.confirm_destructive_actions,
+                            onchange: move |event: Event<FormData>| {
+                                state.write().settings.confirm_destructive_actions = event.checked();
+                            },
+                        }
+                    }
+                }
+                tr {
+                    td {"reduced motion"}
+                    td {
+                        input {
+                            r#type: "checkbox",
+                            checked: settings.reduced_motion,
+                            onchange: move |event: Event<FormData>| {
+                                state.write().settings.reduced_motion = event.checked();
+                                apply_reduced_motion(event.checked());
+                            },
+                        }
+                    }
+                }
+            }
+            NotationSelect {}
+            ThemePicker {}
+            KeybindingsPanel {}
+            button {
+                onclick: move |_| {
+                    save_game(&state.read());
+                    state.write().logs.log(LogCategory::System, "game saved");
+                },
+                "save now"
+            }
+            ReportAction {}
+        }
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
-use crate::state::State;
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::Signal;
@@ -8,18 +9,15 @@ use dioxus::prelude::*;
 use web_time::Instant;
 
 #[component]
-pub(crate) fn SimpleAction(
-    mut state: Signal<State>,
-    button_name: String,
-    action: EventHandler<Signal<State>>,
-) -> Element {
+pub(crate) fn SimpleAction(button_name: String, action: EventHandler<Signal<State>>) -> Element {
+    let mut state = use_game_state();
     let log = format!("clicked on {} button", button_name.clone());
     rsx! {
         button {
             class: "repeatable-action-button",
             onclick: move |_| {
                 action.call(state);
-                state.write().logs.log(log.as_str());
+                state.write().logs.log(LogCategory::Action, log.as_str());
                 if state.read().speedrun_start.is_none() {
                     state.write().speedrun_start = Some(Instant::now());
                 }
@@ -1,45 +1,203 @@
 #![allow(non_snake_case)]
 use crate::constants::Research;
-use crate::state::State;
+use crate::format_duration::format_duration;
+use crate::state::use_game_state;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
 use dioxus::prelude::{Readable, Signal};
-use std::collections::VecDeque;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use web_time::{SystemTime, UNIX_EPOCH};
 
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[repr(u8)]
+pub(crate) enum LogCategory {
+    Action,
+    Research,
+    Quest,
+    Cheat,
+    System,
+    Event,
+}
+
+impl LogCategory {
+    pub(crate) const ALL: [LogCategory; 6] = [
+        LogCategory::Action,
+        LogCategory::Research,
+        LogCategory::Quest,
+        LogCategory::Cheat,
+        LogCategory::System,
+        LogCategory::Event,
+    ];
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            LogCategory::Action => "action",
+            LogCategory::Research => "research",
+            LogCategory::Quest => "quest",
+            LogCategory::Cheat => "cheat",
+            LogCategory::System => "system",
+            LogCategory::Event => "event",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LogEntry {
+    pub category: LogCategory,
+    pub message: String,
+    // consecutive identical messages are collapsed into a single entry
+    pub count: u32,
+    // game time and wall time of the latest occurrence
+    pub game_time: f64,
+    #[serde(
+        serialize_with = "serialize_unix_seconds",
+        deserialize_with = "deserialize_unix_seconds"
+    )]
+    pub wall_time: SystemTime,
+}
+
+fn serialize_unix_seconds<S: Serializer>(
+    wall_time: &SystemTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let seconds = wall_time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |duration| duration.as_secs_f64());
+    serializer.serialize_f64(seconds)
+}
+
+fn deserialize_unix_seconds<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<SystemTime, D::Error> {
+    let seconds = f64::deserialize(deserializer)?;
+    Ok(UNIX_EPOCH + Duration::from_secs_f64(seconds.max(0.0)))
+}
+
+impl LogEntry {
+    pub(crate) fn render(&self) -> String {
+        let wall_seconds = self
+            .wall_time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let repeat = if self.count > 1 {
+            format!(" x{}", self.count)
+        } else {
+            "".to_string()
+        };
+        format!(
+            "[{} | {:02}:{:02}:{:02} UTC] {}: {}{}",
+            format_duration(self.game_time),
+            (wall_seconds / 3600) % 24,
+            (wall_seconds / 60) % 60,
+            wall_seconds % 60,
+            self.category.name(),
+            self.message,
+            repeat
+        )
+    }
+}
+
+pub(crate) const DEFAULT_MAX_LINES: usize = 100;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub(crate) struct SimpleLogs {
     max_lines: usize,
-    lines: VecDeque<String>,
+    // game time of the last `State::update`, used to timestamp entries
+    game_time: f64,
+    lines: VecDeque<LogEntry>,
 }
 
 impl SimpleLogs {
     pub(crate) fn new() -> Self {
         SimpleLogs {
-            max_lines: 5,
+            max_lines: DEFAULT_MAX_LINES,
+            game_time: 0.0,
             lines: VecDeque::new(),
         }
     }
 
-    pub(crate) fn render(&self) -> String {
-        let lines: Vec<String> = self.lines.iter().cloned().collect();
-        lines.join("\n")
+    pub(crate) fn set_max_lines(&mut self, max_lines: usize) {
+        self.max_lines = max_lines.max(1);
+        while self.lines.len() > self.max_lines {
+            self.lines.pop_front();
+        }
     }
 
-    pub(crate) fn log(&mut self, message: &str) {
+    pub(crate) fn set_game_time(&mut self, game_time: f64) {
+        self.game_time = game_time;
+    }
+
+    pub(crate) fn entries(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.lines.iter()
+    }
+
+    pub(crate) fn log(&mut self, category: LogCategory, message: &str) {
+        if let Some(last) = self.lines.back_mut() {
+            if last.category == category && last.message == message {
+                last.count += 1;
+                last.game_time = self.game_time;
+                last.wall_time = SystemTime::now();
+                return;
+            }
+        }
         if self.lines.len() >= self.max_lines {
             self.lines.pop_front();
         }
-        self.lines.push_back(message.to_string());
+        self.lines.push_back(LogEntry {
+            category,
+            message: message.to_string(),
+            count: 1,
+            game_time: self.game_time,
+            wall_time: SystemTime::now(),
+        });
     }
 }
 
 #[component]
-pub(crate) fn Logs(state: Signal<State>) -> Element {
+pub(crate) fn Logs() -> Element {
+    let state = use_game_state();
+    let mut hidden_categories: Signal<HashSet<LogCategory>> = use_signal(HashSet::new);
+    // newest first
+    let lines: Vec<String> = state
+        .read()
+        .logs
+        .entries()
+        .rev()
+        .filter(|entry| !hidden_categories.read().contains(&entry.category))
+        .map(LogEntry::render)
+        .collect();
     rsx! {
         if state.read().researched.contains(&Research::Logs) {
             div {
-                class: "logs",
-                {state.read().logs.render()}
+                class: "logs-panel",
+                div {
+                    class: "logs-filters",
+                    for category in LogCategory::ALL {
+                        label {
+                            input {
+                                r#type: "checkbox",
+                                checked: !hidden_categories.read().contains(&category),
+                                onchange: move |event: Event<FormData>| {
+                                    if event.checked() {
+                                        hidden_categories.write().remove(&category);
+                                    } else {
+                                        hidden_categories.write().insert(category);
+                                    }
+                                },
+                            }
+                            "{category.name()}"
+                        }
+                    }
+                }
+                div {
+                    class: "logs",
+                    for line in lines {
+                        div {"{line}"}
+                    }
+                }
             }
         }
     }
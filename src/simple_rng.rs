@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/**
+ * Small deterministic pseudo random number generator (SplitMix64).
+ * The seed is stored in `State` so that a run can be replayed exactly.
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SimpleRng {
+    state: u64,
+}
+
+impl SimpleRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        SimpleRng { state: seed }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// uniform in [0, 1)
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// uniform in [min, max)
+    pub(crate) fn range_f64(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    /// uniform in [0, len)
+    pub(crate) fn index(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
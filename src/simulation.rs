@@ -0,0 +1,451 @@
+//! Headless runs of the game: no UI, no wall clock and a fixed random seed, so that a run is reproducible.
+//! A strategy picks the actions performed before every tick, the run keeps them as a replayable route.
+
+use crate::actions::Action;
+use crate::constants::{GameConstants, Research};
+use crate::modifiers::Stat;
+use crate::producers::Producer;
+use crate::quest_data::quest_definitions;
+use crate::random_events::RandomEvents;
+use crate::research_data::{research_definitions, ResearchDefinition};
+use crate::simple_rng::SimpleRng;
+use crate::state::State;
+use break_infinity::Decimal;
+use std::cmp::Ordering;
+
+// same as the default tick setting of 100ms
+pub(crate) const TICKS_PER_SECOND: f64 = 10.0;
+// the reference strategy is the yardstick of balance reports and regression tests
+pub(crate) const REFERENCE_SEED: u64 = 42;
+pub(crate) const REFERENCE_CLICKS_PER_SECOND: f64 = 5.0;
+
+/**
+ * Decides what to do before each tick, from what a player could see
+ */
+pub(crate) trait Strategy {
+    fn actions(&mut self, state: &State, constants: &GameConstants) -> Vec<Action>;
+}
+
+#[derive(Clone)]
+pub(crate) struct Simulation {
+    pub state: State,
+    pub constants: GameConstants,
+    pub ticks: u64,
+    // every action performed, with the tick it was performed before
+    pub route: Vec<(u64, Action)>,
+    // game time at which each quest was completed, in completion order
+    pub quests: Vec<(Research, f64)>,
+}
+
+impl Simulation {
+    pub(crate) fn new(constants: GameConstants, seed: u64) -> Self {
+        let mut state = State::new(constants.clone());
+        state.rng_seed = seed;
+        state.rng = SimpleRng::new(seed);
+        state.random_events = RandomEvents::new(&constants, &mut state.rng);
+        Simulation {
+            state,
+            constants,
+            ticks: 0,
+            route: Vec::new(),
+            quests: Vec::new(),
+        }
+    }
+
+    pub(crate) fn perform(&mut self, action: Action) -> bool {
+        if !action.apply(&mut self.state, &self.constants) {
+            return false;
+        }
+        self.route.push((self.ticks, action));
+        if let Action::Research(research) = action {
+            if quest_definitions(&self.constants)
+                .iter()
+                .any(|definition| definition.research == research)
+            {
+                self.quests.push((research, self.state.game_time));
+            }
+        }
+        true
+    }
+
+    /// game time at which `quest` was completed, if it was
+    pub(crate) fn completed(&self, quest: Research) -> Option<f64> {
+        self.quests
+            .iter()
+            .find(|(completed, _)| *completed == quest)
+            .map(|(_, seconds)| *seconds)
+    }
+
+    pub(crate) fn tick(&mut self) {
+        // events are dismissed with their free choice, as by a player away from the keyboard
+        if let Some(event) = self.state.random_events.pending {
+            let choices = event.choices(&self.state, &self.constants);
+            event.resolve(choices.len() - 1, &mut self.state, &self.constants);
+        }
+        self.state.update(Decimal::new(TICKS_PER_SECOND));
+        self.ticks += 1;
+    }
+
+    /**
+     * Plays `strategy` until `goal` is researched or `max_seconds` of game time have elapsed
+     */
+    pub(crate) fn run(&mut self, strategy: &mut impl Strategy, goal: Research, max_seconds: f64) {
+        while !self.state.researched.contains(&goal) && self.state.game_time < max_seconds {
+            for action in strategy.actions(&self.state, &self.constants) {
+                self.perform(action);
+            }
+            self.tick();
+        }
+    }
+}
+
+/**
+ * Loc gained per tick by one more hire of `producer`, per tick elapsed since the hire for HRs.
+ * Zero for producers that do not write code (PMs).
+ */
+pub(crate) fn loc_per_hire(producer: Producer, state: &State) -> Decimal {
+    let dt = state.dt;
+    let interns = state.effective(Stat::InternsLocDt) * dt;
+    let junior_devs = state.effective(Stat::JuniorDevsLocDt) * dt;
+    let senior_devs = state.effective(Stat::SeniorDevsLocDt) * dt;
+    match producer {
+        Producer::Interns => interns,
+        Producer::JuniorDevs => junior_devs,
+        Producer::SeniorDevs => senior_devs,
+        Producer::Hrs => {
+            (state.effective(Stat::HrsInternsDt) * state.hrs_interns_quota * interns
+                + state.effective(Stat::HrsJuniorDevsDt)
+                    * state.hrs_junior_devs_quota
+                    * junior_devs
+                + state.effective(Stat::HrsSeniorDevsDt)
+                    * state.hrs_senior_devs_quota
+                    * senior_devs)
+                * dt
+        }
+        Producer::Pms => Decimal::ZERO,
+    }
+}
+
+/**
+ * Game time for the next hire of `producer` to produce its own cost, `None` if it never does.
+ * HRs make production grow linearly, so they pay back in sqrt(2 * cost / growth).
+ */
+pub(crate) fn payback_seconds(
+    producer: Producer,
+    state: &State,
+    constants: &GameConstants,
+) -> Option<f64> {
+    let loc = loc_per_hire(producer, state).to_number();
+    if loc.partial_cmp(&0.0) != Some(Ordering::Greater) {
+        return None;
+    }
+    let cost = producer.next_loc_cost(state, constants).to_number();
+    let ticks = match producer {
+        Producer::Hrs => (2.0 * cost / loc).sqrt(),
+        _ => cost / loc,
+    };
+    let seconds = ticks / TICKS_PER_SECOND;
+    seconds.is_finite().then_some(seconds)
+}
+
+/**
+ * Researches and quests that can be bought now, cheapest first
+ */
+pub(crate) fn available_researches(
+    state: &State,
+    constants: &GameConstants,
+) -> Vec<ResearchDefinition> {
+    let mut definitions: Vec<_> = research_definitions(constants)
+        .into_iter()
+        .chain(quest_definitions(constants))
+        .filter(|definition| Action::Research(definition.research).is_available(state, constants))
+        .collect();
+    definitions.sort_by(|a, b| {
+        a.loc_cost
+            .partial_cmp(&b.loc_cost)
+            .unwrap_or(Ordering::Equal)
+    });
+    definitions
+}
+
+/**
+ * A steady player: clicks at a fixed pace and buys every research and quest as soon as it is
+ * affordable. In between it hires whichever producer pays back the fastest, as long as the hire
+ * pays back before the next quest would have been affordable without it.
+ */
+#[derive(Clone)]
+pub(crate) struct ReferenceStrategy {
+    pub clicks_per_second: f64,
+    // never bought, to measure what they are worth
+    pub skipped: Vec<Research>,
+    // fraction of a click carried over to the next tick
+    clicks: f64,
+}
+
+impl ReferenceStrategy {
+    pub(crate) fn new(clicks_per_second: f64) -> Self {
+        ReferenceStrategy {
+            clicks_per_second,
+            skipped: Vec::new(),
+            clicks: 0.0,
+        }
+    }
+
+    pub(crate) fn skipping(mut self, research: Research) -> Self {
+        self.skipped.push(research);
+        self
+    }
+}
+
+impl Strategy for ReferenceStrategy {
+    fn actions(&mut self, state: &State, constants: &GameConstants) -> Vec<Action> {
+        let mut actions = Vec::new();
+        self.clicks += self.clicks_per_second / TICKS_PER_SECOND;
+        while self.clicks >= 1.0 {
+            self.clicks -= 1.0;
+            actions.push(Action::Code);
+        }
+        // decisions are made on the loc left once the clicks are in
+        let mut loc = state.loc + state.loc_per_clicks * Decimal::new(actions.len() as f64);
+        for definition in available_researches(state, constants)
+            .into_iter()
+            .filter(|definition| !self.skipped.contains(&definition.research))
+        {
+            if loc < definition.loc_cost {
+                break;
+            }
+            loc -= definition.loc_cost;
+            actions.push(Action::Research(definition.research));
+        }
+        let seconds_to_next_quest = quest_definitions(constants)
+            .into_iter()
+            .find(|definition| Action::Research(definition.research).is_available(state, constants))
+            .map_or(f64::INFINITY, |quest| {
+                ((quest.loc_cost - loc) / state.loc_dt).to_number()
+            });
+        let best = Producer::ALL
+            .into_iter()
+            .filter(|producer| Action::Hire(*producer).is_available(state, constants))
+            .filter_map(|producer| {
+                payback_seconds(producer, state, constants).map(|seconds| (producer, seconds))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((producer, seconds)) = best {
+            // NaN before any production: nothing is worth saving for yet
+            let worth_it = seconds_to_next_quest.partial_cmp(&seconds) != Some(Ordering::Less);
+            if worth_it && loc >= producer.next_loc_cost(state, constants) {
+                actions.push(Action::Hire(producer));
+            }
+        }
+        actions
+    }
+}
+
+/**
+ * Performs again the actions of a recorded route, at the same ticks
+ */
+pub(crate) struct Replay {
+    route: Vec<(u64, Action)>,
+    next: usize,
+    tick: u64,
+}
+
+impl Replay {
+    pub(crate) fn new(route: Vec<(u64, Action)>) -> Self {
+        Replay {
+            route,
+            next: 0,
+            tick: 0,
+        }
+    }
+}
+
+impl Strategy for Replay {
+    fn actions(&mut self, _state: &State, _constants: &GameConstants) -> Vec<Action> {
+        let start = self.next;
+        while self
+            .route
+            .get(self.next)
+            .is_some_and(|(tick, _)| *tick == self.tick)
+        {
+            self.next += 1;
+        }
+        self.tick += 1;
+        self.route[start..self.next]
+            .iter()
+            .map(|(_, action)| *action)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eta::format_seconds;
+    use std::fmt::Write;
+
+    // the normal preset plateaus before the last quests, left out of the golden file
+    const MAX_SECONDS: f64 = 4.0 * 3600.0;
+    const GOLDEN_PATH: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/golden/quest_timings.txt"
+    );
+    // a milestone may move by this ratio, or by a few seconds early on, before the test fails
+    const TOLERANCE_RATIO: f64 = 0.05;
+    const TOLERANCE_SECONDS: f64 = 2.0;
+
+    fn reference_run() -> Simulation {
+        let mut simulation = Simulation::new(GameConstants::default(), REFERENCE_SEED);
+        let mut strategy = ReferenceStrategy::new(REFERENCE_CLICKS_PER_SECOND);
+        simulation.run(&mut strategy, Research::Differentiation, MAX_SECONDS);
+        simulation
+    }
+
+    /// game time to each quest, `None` when not completed within `MAX_SECONDS`
+    fn quest_timings(simulation: &Simulation) -> Vec<(String, Option<f64>)> {
+        quest_definitions(&simulation.constants)
+            .iter()
+            .map(|definition| {
+                (
+                    format!("{:?}", definition.research),
+                    simulation.completed(definition.research),
+                )
+            })
+            .collect()
+    }
+
+    /// only completed quests are written, a quest missing from the file was not completed
+    fn parse_golden(golden: &str) -> Vec<(String, f64)> {
+        golden
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (quest, seconds) = line.split_once(' ').expect("quest and seconds");
+                (
+                    quest.to_string(),
+                    seconds.trim().parse().expect("seconds to the quest"),
+                )
+            })
+            .collect()
+    }
+
+    fn format_golden(timings: &[(String, Option<f64>)]) -> String {
+        let mut golden = String::new();
+        writeln!(
+            golden,
+            "# game time in seconds to complete each quest with the reference strategy,"
+        )
+        .unwrap();
+        writeln!(
+            golden,
+            "# quests not completed within {} seconds are left out",
+            MAX_SECONDS
+        )
+        .unwrap();
+        writeln!(
+            golden,
+            "# regenerate with UPDATE_GOLDEN=1 cargo test quest_timings"
+        )
+        .unwrap();
+        for (quest, seconds) in timings {
+            if let Some(seconds) = seconds {
+                writeln!(golden, "{} {}", quest, format_seconds(Some(*seconds))).unwrap();
+            }
+        }
+        golden
+    }
+
+    fn golden_seconds(golden: &[(String, f64)], quest: &str) -> Option<f64> {
+        golden
+            .iter()
+            .find(|(golden_quest, _)| golden_quest == quest)
+            .map(|(_, seconds)| *seconds)
+    }
+
+    /// a quest completed in only one of the runs has moved too
+    fn within_tolerance(golden: Option<f64>, actual: Option<f64>) -> bool {
+        match (golden, actual) {
+            (Some(golden), Some(actual)) => {
+                (actual - golden).abs() <= (golden * TOLERANCE_RATIO).max(TOLERANCE_SECONDS)
+            }
+            (golden, actual) => golden.is_none() && actual.is_none(),
+        }
+    }
+
+    /**
+     * One line per quest completed in either run, the ones that moved too much are marked with a `!`
+     */
+    fn timings_diff(golden: &[(String, f64)], actual: &[(String, Option<f64>)]) -> String {
+        let mut diff = format!(
+            "  {:<16} {:>10} {:>10} {:>8}\n",
+            "quest", "golden", "actual", "delta"
+        );
+        for (quest, actual_seconds) in actual {
+            let golden_seconds = golden_seconds(golden, quest);
+            if golden_seconds.is_none() && actual_seconds.is_none() {
+                continue;
+            }
+            let delta = match (golden_seconds, actual_seconds) {
+                (Some(golden), Some(actual)) => format!("{:+.1}%", (actual / golden - 1.0) * 100.0),
+                _ => String::new(),
+            };
+            let marker = if within_tolerance(golden_seconds, *actual_seconds) {
+                ' '
+            } else {
+                '!'
+            };
+            writeln!(
+                diff,
+                "{} {:<16} {:>10} {:>10} {:>8}",
+                marker,
+                quest,
+                format_seconds(golden_seconds),
+                format_seconds(*actual_seconds),
+                delta
+            )
+            .unwrap();
+        }
+        diff
+    }
+
+    #[test]
+    fn quest_timings_match_golden() {
+        let actual = quest_timings(&reference_run());
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(GOLDEN_PATH, format_golden(&actual)).unwrap();
+            return;
+        }
+        let golden = parse_golden(&std::fs::read_to_string(GOLDEN_PATH).unwrap());
+        let moved = actual
+            .iter()
+            .any(|(quest, seconds)| !within_tolerance(golden_seconds(&golden, quest), *seconds));
+        assert!(
+            !moved,
+            "quest timings moved by more than {}% (or {}s) from {}:\n{}\
+            rerun with UPDATE_GOLDEN=1 if the new balance is intended",
+            TOLERANCE_RATIO * 100.0,
+            TOLERANCE_SECONDS,
+            GOLDEN_PATH,
+            timings_diff(&golden, &actual)
+        );
+    }
+
+    #[test]
+    fn replaying_a_route_gives_the_same_run() {
+        let mut original = Simulation::new(GameConstants::default(), REFERENCE_SEED);
+        original.run(
+            &mut ReferenceStrategy::new(REFERENCE_CLICKS_PER_SECOND),
+            Research::Differentiation,
+            600.0,
+        );
+        let mut replayed = Simulation::new(GameConstants::default(), REFERENCE_SEED);
+        replayed.run(
+            &mut Replay::new(original.route.clone()),
+            Research::Differentiation,
+            600.0,
+        );
+        assert_eq!(replayed.route, original.route);
+        assert_eq!(replayed.quests, original.quests);
+        assert_eq!(replayed.state.loc, original.state.loc);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::state::State;
+use break_infinity::Decimal;
+use serde::{Deserialize, Serialize};
+
+/**
+ * Numeric fields of `State` that can be captured and restored
+ */
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub(crate) enum StateField {
+    Loc,
+    Bugs,
+    Features,
+    Interns,
+    ManualInterns,
+    JuniorDevs,
+    ManualJuniorDevs,
+    SeniorDevs,
+    ManualSeniorDevs,
+    Hrs,
+    ManualHrs,
+    Pms,
+    ManualPms,
+    RetiredDevs,
+}
+
+impl StateField {
+    #[cfg(any(test, feature = "cheats"))]
+    pub(crate) const ALL: [StateField; 14] = [
+        StateField::Loc,
+        StateField::Bugs,
+        StateField::Features,
+        StateField::Interns,
+        StateField::ManualInterns,
+        StateField::JuniorDevs,
+        StateField::ManualJuniorDevs,
+        StateField::SeniorDevs,
+        StateField::ManualSeniorDevs,
+        StateField::Hrs,
+        StateField::ManualHrs,
+        StateField::Pms,
+        StateField::ManualPms,
+        StateField::RetiredDevs,
+    ];
+
+    // what a git commit captures and git revert restores
+    pub(crate) const CODEBASE: [StateField; 3] =
+        [StateField::Loc, StateField::Bugs, StateField::Features];
+
+    // what smart staffing changes: every dev tier, auto and manual, and loc for the severance
+    pub(crate) const LAYOFFS: [StateField; 7] = [
+        StateField::Loc,
+        StateField::Interns,
+        StateField::ManualInterns,
+        StateField::JuniorDevs,
+        StateField::ManualJuniorDevs,
+        StateField::SeniorDevs,
+        StateField::ManualSeniorDevs,
+    ];
+
+    /// same name as the `State` field
+    #[cfg(any(test, feature = "cheats"))]
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            StateField::Loc => "loc",
+            StateField::Bugs => "bugs",
+            StateField::Features => "features",
+            StateField::Interns => "interns",
+            StateField::ManualInterns => "manual_interns",
+            StateField::JuniorDevs => "junior_devs",
+            StateField::ManualJuniorDevs => "manual_junior_devs",
+            StateField::SeniorDevs => "senior_devs",
+            StateField::ManualSeniorDevs => "manual_senior_devs",
+            StateField::Hrs => "hrs",
+            StateField::ManualHrs => "manual_hrs",
+            StateField::Pms => "pms",
+            StateField::ManualPms => "manual_pms",
+            StateField::RetiredDevs => "retired_devs",
+        }
+    }
+
+    pub(crate) fn get(&self, state: &State) -> Decimal {
+        match self {
+            StateField::Loc => state.loc,
+            StateField::Bugs => state.bugs,
+            StateField::Features => state.features,
+            StateField::Interns => state.interns,
+            StateField::ManualInterns => state.manual_interns,
+            StateField::JuniorDevs => state.junior_devs,
+            StateField::ManualJuniorDevs => state.manual_junior_devs,
+            StateField::SeniorDevs => state.senior_devs,
+            StateField::ManualSeniorDevs => state.manual_senior_devs,
+            StateField::Hrs => state.hrs,
+            StateField::ManualHrs => state.manual_hrs,
+            StateField::Pms => state.pms,
+            StateField::ManualPms => state.manual_pms,
+            StateField::RetiredDevs => state.retired_devs,
+        }
+    }
+
+    pub(crate) fn get_mut<'a>(&self, state: &'a mut State) -> &'a mut Decimal {
+        match self {
+            StateField::Loc => &mut state.loc,
+            StateField::Bugs => &mut state.bugs,
+            StateField::Features => &mut state.features,
+            StateField::Interns => &mut state.interns,
+            StateField::ManualInterns => &mut state.manual_interns,
+            StateField::JuniorDevs => &mut state.junior_devs,
+            StateField::ManualJuniorDevs => &mut state.manual_junior_devs,
+            StateField::SeniorDevs => &mut state.senior_devs,
+            StateField::ManualSeniorDevs => &mut state.manual_senior_devs,
+            StateField::Hrs => &mut state.hrs,
+            StateField::ManualHrs => &mut state.manual_hrs,
+            StateField::Pms => &mut state.pms,
+            StateField::ManualPms => &mut state.manual_pms,
+            StateField::RetiredDevs => &mut state.retired_devs,
+        }
+    }
+}
+
+/**
+ * Values of a few `State` fields at some point in time
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Snapshot {
+    values: Vec<(StateField, Decimal)>,
+}
+
+impl Snapshot {
+    pub(crate) fn take(state: &State, fields: &[StateField]) -> Self {
+        Snapshot {
+            values: fields
+                .iter()
+                .map(|field| (*field, field.get(state)))
+                .collect(),
+        }
+    }
+
+    /// captured value of `field`, if it was captured
+    pub(crate) fn get(&self, field: StateField) -> Option<Decimal> {
+        self.values
+            .iter()
+            .find(|(captured, _)| *captured == field)
+            .map(|(_, value)| *value)
+    }
+
+    /// how much each captured field changed since the snapshot
+    pub(crate) fn changes_since(&self, state: &State) -> Self {
+        Snapshot {
+            values: self
+                .values
+                .iter()
+                .map(|(field, value)| (*field, field.get(state) - *value))
+                .collect(),
+        }
+    }
+
+    /**
+     * Takes `changes_since` back out of `state`, keeping whatever else happened to its fields.
+     * Refuses, leaving `state` untouched, when a field would go negative.
+     */
+    pub(crate) fn revert_changes(&self, state: &mut State) -> bool {
+        let reverted: Vec<(StateField, Decimal)> = self
+            .values
+            .iter()
+            .map(|(field, change)| (*field, field.get(state) - *change))
+            .collect();
+        if reverted.iter().any(|(_, value)| *value < Decimal::ZERO) {
+            return false;
+        }
+        for (field, value) in reverted {
+            *field.get_mut(state) = value;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::GameConstants;
+
+    #[test]
+    fn reverting_changes_only_touches_captured_fields() {
+        let mut state = State::new(GameConstants::default());
+        state.loc = Decimal::new(100.0);
+        state.bugs = Decimal::new(10.0);
+        let snapshot = Snapshot::take(&state, &[StateField::Loc]);
+        state.loc = Decimal::ZERO;
+        state.bugs = Decimal::ZERO;
+        let changes = snapshot.changes_since(&state);
+        // written since, and kept
+        state.loc += Decimal::new(5.0);
+        assert!(changes.revert_changes(&mut state));
+        assert_eq!(state.loc, Decimal::new(105.0));
+        assert_eq!(state.bugs, Decimal::ZERO);
+
+        let snapshot = Snapshot::take(&state, &[StateField::Loc]);
+        state.loc += Decimal::new(500.0);
+        let changes = snapshot.changes_since(&state);
+        // the loc the change gave was spent since
+        state.loc = Decimal::new(10.0);
+        assert!(!changes.revert_changes(&mut state));
+        assert_eq!(state.loc, Decimal::new(10.0));
+    }
+}
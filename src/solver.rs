@@ -0,0 +1,395 @@
+//! Automated player looking for the fastest route to a quest. It starts from the greedy payback
+//! reference strategy, then runs a beam search over plans switched every few seconds of game time.
+//! Routes that look too good to be true are reported, they usually exploit a balance hole.
+
+use crate::actions::Action;
+use crate::constants::{GameConstants, Research};
+use crate::producers::Producer;
+use crate::quest_data::quest_definitions;
+use crate::research_data::ResearchDefinition;
+use crate::simulation::{
+    ReferenceStrategy, Simulation, Strategy, REFERENCE_CLICKS_PER_SECOND, REFERENCE_SEED,
+};
+use crate::state::State;
+use break_infinity::Decimal;
+use std::cmp::Ordering;
+
+// game time between two decisions of the solver
+const EPOCH_SECONDS: f64 = 30.0;
+// quests past the most advanced route that candidates are compared on: the next one only is
+// too short sighted to invest in production, the goal only never saves up for quests
+const QUESTS_AHEAD: usize = 2;
+// beating the reference strategy by this much smells like an exploit
+const DEGENERATE_SPEEDUP: f64 = 10.0;
+// a sane route has little use for layoffs, wiping the codebase or reverting it
+const MAX_DESTRUCTIVE_ACTIONS: usize = 3;
+
+/**
+ * What the player does until the next decision, on top of clicking and buying researches and quests
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Plan {
+    // hires the fastest payback, unless the next quest is closer
+    Payback,
+    // hires nobody
+    Save,
+    // hires as many as affordable of a single producer
+    Hire(Producer),
+    // lays off with smart staffing first, then hires the fastest payback
+    SmartStaffing,
+}
+
+impl Plan {
+    pub(crate) const ALL: [Plan; 7] = [
+        Plan::Payback,
+        Plan::Save,
+        Plan::Hire(Producer::Interns),
+        Plan::Hire(Producer::JuniorDevs),
+        Plan::Hire(Producer::SeniorDevs),
+        Plan::Hire(Producer::Hrs),
+        Plan::SmartStaffing,
+    ];
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            Plan::Payback => "payback".to_string(),
+            Plan::Save => "save".to_string(),
+            Plan::Hire(producer) => format!("hire {}", producer.name()),
+            Plan::SmartStaffing => "smart staffing".to_string(),
+        }
+    }
+
+    fn is_available(&self, state: &State, constants: &GameConstants) -> bool {
+        match self {
+            Plan::Payback | Plan::Save => true,
+            Plan::Hire(producer) => Action::Hire(*producer).is_available(state, constants),
+            Plan::SmartStaffing => Action::SmartStaffing.is_available(state, constants),
+        }
+    }
+}
+
+/**
+ * Follows a plan, clicks and purchases of researches and quests are the reference ones
+ */
+struct Planned {
+    plan: Plan,
+    reference: ReferenceStrategy,
+    started: bool,
+}
+
+impl Planned {
+    fn new(plan: Plan) -> Self {
+        Planned {
+            plan,
+            reference: ReferenceStrategy::new(REFERENCE_CLICKS_PER_SECOND),
+            started: false,
+        }
+    }
+}
+
+impl Strategy for Planned {
+    fn actions(&mut self, state: &State, constants: &GameConstants) -> Vec<Action> {
+        let mut actions = Vec::new();
+        if !self.started && self.plan == Plan::SmartStaffing {
+            actions.push(Action::SmartStaffing);
+        }
+        self.started = true;
+        let reference = self.reference.actions(state, constants);
+        match self.plan {
+            Plan::Payback | Plan::SmartStaffing => actions.extend(reference),
+            Plan::Save | Plan::Hire(_) => actions.extend(
+                reference
+                    .into_iter()
+                    .filter(|action| !matches!(action, Action::Hire(_))),
+            ),
+        }
+        if let Plan::Hire(producer) = self.plan {
+            actions.push(Action::HireMax(producer));
+        }
+        actions
+    }
+}
+
+/**
+ * Game time at which `simulation` could afford every quest up to the `target`-th one at its
+ * current production. Quests give nothing back, so the further the target the more this
+ * values production over loc in hand.
+ */
+fn eta(simulation: &Simulation, quests: &[ResearchDefinition], target: usize) -> f64 {
+    let state = &simulation.state;
+    let remaining = quests[simulation.quests.len().min(target)..=target]
+        .iter()
+        .fold(Decimal::ZERO, |sum, quest| sum + quest.loc_cost);
+    let missing = (remaining - state.loc).max(&Decimal::ZERO);
+    if missing == Decimal::ZERO {
+        return state.game_time;
+    }
+    let seconds = (missing / state.loc_dt).to_number();
+    // no production yet
+    if seconds.is_nan() {
+        return f64::INFINITY;
+    }
+    state.game_time + seconds
+}
+
+/**
+ * Best routes to `goal` first: the ones completing it the soonest, then the ones that could
+ * afford the quests a little past the most advanced route the soonest
+ */
+fn sort_routes<T>(
+    routes: &mut [T],
+    simulation: impl Fn(&T) -> &Simulation,
+    goal: Research,
+    quests: &[ResearchDefinition],
+) {
+    let Some(goal_index) = quests.iter().position(|quest| quest.research == goal) else {
+        return;
+    };
+    let furthest = routes
+        .iter()
+        .map(|route| simulation(route).quests.len())
+        .max()
+        .unwrap_or(0);
+    let target = (furthest + QUESTS_AHEAD).min(goal_index);
+    routes.sort_by(|a, b| {
+        let (a, b) = (simulation(a), simulation(b));
+        match (a.completed(goal), b.completed(goal)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => eta(a, quests, target).total_cmp(&eta(b, quests, target)),
+        }
+    });
+}
+
+#[derive(Clone)]
+struct Candidate {
+    simulation: Simulation,
+    plans: Vec<Plan>,
+}
+
+pub(crate) struct Solution {
+    pub goal: Research,
+    // the reference strategy, as a baseline
+    pub reference: Simulation,
+    pub best: Simulation,
+    // plan of each epoch of `best`, empty when the reference strategy could not be beaten
+    pub plans: Vec<Plan>,
+}
+
+/**
+ * Searches for the route completing `goal` the soonest, or getting the furthest within `max_seconds`
+ */
+pub(crate) fn solve(
+    constants: &GameConstants,
+    goal: Research,
+    max_seconds: f64,
+    beam_width: usize,
+) -> Solution {
+    let quests = quest_definitions(constants);
+    let mut reference = Simulation::new(constants.clone(), REFERENCE_SEED);
+    reference.run(
+        &mut ReferenceStrategy::new(REFERENCE_CLICKS_PER_SECOND),
+        goal,
+        max_seconds,
+    );
+
+    let mut beam = vec![Candidate {
+        simulation: Simulation::new(constants.clone(), REFERENCE_SEED),
+        plans: Vec::new(),
+    }];
+    // the beam is kept sorted, best first
+    let best = loop {
+        let best = &beam[0].simulation;
+        if best.completed(goal).is_some() || best.state.game_time >= max_seconds {
+            break beam.swap_remove(0);
+        }
+        let mut next = Vec::new();
+        for candidate in &beam {
+            let until = (candidate.simulation.state.game_time + EPOCH_SECONDS).min(max_seconds);
+            for plan in Plan::ALL {
+                if !plan.is_available(&candidate.simulation.state, constants) {
+                    continue;
+                }
+                let mut simulation = candidate.simulation.clone();
+                simulation.run(&mut Planned::new(plan), goal, until);
+                let mut plans = candidate.plans.clone();
+                plans.push(plan);
+                next.push(Candidate { simulation, plans });
+            }
+        }
+        sort_routes(&mut next, |candidate| &candidate.simulation, goal, &quests);
+        next.truncate(beam_width);
+        beam = next;
+    };
+
+    let mut finalists = [&best.simulation, &reference];
+    sort_routes(&mut finalists, |simulation| simulation, goal, &quests);
+    // ties go to the reference strategy
+    let beaten =
+        std::ptr::eq(finalists[0], &best.simulation) && best.simulation.route != reference.route;
+    if beaten {
+        Solution {
+            goal,
+            reference,
+            best: best.simulation,
+            plans: best.plans,
+        }
+    } else {
+        Solution {
+            goal,
+            best: reference.clone(),
+            reference,
+            plans: Vec::new(),
+        }
+    }
+}
+
+/// destructive actions counted together whatever their target, every git revert alike
+fn destructive_kind(action: &Action) -> Option<&'static str> {
+    match action {
+        Action::SmartStaffing => Some("smart staffing"),
+        Action::Rmrf => Some("rm -rf"),
+        Action::GitRevert(_) => Some("git revert"),
+        _ => None,
+    }
+}
+
+/**
+ * What looks like an exploit in the best route: repeated destructive actions, or a speedup
+ * over the reference strategy no honest purchase order should give
+ */
+pub(crate) fn degenerate_findings(solution: &Solution) -> Vec<String> {
+    let mut findings = Vec::new();
+    for kind in ["smart staffing", "rm -rf", "git revert"] {
+        let count = solution
+            .best
+            .route
+            .iter()
+            .filter(|(_, action)| destructive_kind(action) == Some(kind))
+            .count();
+        if count > MAX_DESTRUCTIVE_ACTIONS {
+            findings.push(format!("{} performed {} times", kind, count));
+        }
+    }
+    if let (Some(best), Some(reference)) = (
+        solution.best.completed(solution.goal),
+        solution.reference.completed(solution.goal),
+    ) {
+        if best * DEGENERATE_SPEEDUP < reference {
+            findings.push(format!(
+                "{:?} completed in {:.1}s, more than {}x faster than the reference strategy ({:.1}s)",
+                solution.goal, best, DEGENERATE_SPEEDUP, reference
+            ));
+        }
+    }
+    findings
+}
+
+/**
+ * Plans in order, consecutive epochs with the same plan merged: "payback x12, save x2"
+ */
+pub(crate) fn format_plans(plans: &[Plan]) -> String {
+    let mut runs: Vec<(Plan, usize)> = Vec::new();
+    for plan in plans {
+        match runs.last_mut() {
+            Some((last, count)) if last == plan => *count += 1,
+            _ => runs.push((*plan, 1)),
+        }
+    }
+    runs.iter()
+        .map(|(plan, count)| format!("{} x{}", plan.name(), count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Replay;
+
+    #[test]
+    fn solver_is_never_slower_than_the_reference_and_replays() {
+        let constants = GameConstants::default();
+        let solution = solve(&constants, Research::TextEditor, 600.0, 3);
+        let best = solution.best.completed(Research::TextEditor).unwrap();
+        assert!(best <= solution.reference.completed(Research::TextEditor).unwrap());
+        assert!(
+            degenerate_findings(&solution).is_empty(),
+            "{:?}",
+            degenerate_findings(&solution)
+        );
+
+        let mut replayed = Simulation::new(constants, REFERENCE_SEED);
+        replayed.run(
+            &mut Replay::new(solution.best.route.clone()),
+            Research::TextEditor,
+            600.0,
+        );
+        assert_eq!(replayed.completed(Research::TextEditor), Some(best));
+    }
+
+    #[test]
+    fn repeated_layoffs_are_reported() {
+        let constants = GameConstants::default();
+        let mut best = Simulation::new(constants.clone(), REFERENCE_SEED);
+        best.state.researched.insert(Research::SmartStaffing);
+        for _ in 0..=MAX_DESTRUCTIVE_ACTIONS {
+            assert!(best.perform(Action::SmartStaffing));
+        }
+        let solution = Solution {
+            goal: Research::Differentiation,
+            reference: Simulation::new(constants, REFERENCE_SEED),
+            best,
+            plans: Vec::new(),
+        };
+        assert_eq!(
+            degenerate_findings(&solution),
+            vec![format!(
+                "smart staffing performed {} times",
+                MAX_DESTRUCTIVE_ACTIONS + 1
+            )]
+        );
+    }
+
+    #[test]
+    fn repeated_reverts_are_reported_whatever_the_commit() {
+        let constants = GameConstants::default();
+        let mut best = Simulation::new(constants.clone(), REFERENCE_SEED);
+        best.state
+            .researched
+            .extend([Research::GitCommit, Research::GitRevert]);
+        assert!(best.perform(Action::GitCommit));
+        assert!(best.perform(Action::GitCommit));
+        for index in 0..=MAX_DESTRUCTIVE_ACTIONS {
+            assert!(best.perform(Action::GitRevert(index % 2)));
+        }
+        let solution = Solution {
+            goal: Research::Differentiation,
+            reference: Simulation::new(constants, REFERENCE_SEED),
+            best,
+            plans: Vec::new(),
+        };
+        assert_eq!(
+            degenerate_findings(&solution),
+            vec![format!(
+                "git revert performed {} times",
+                MAX_DESTRUCTIVE_ACTIONS + 1
+            )]
+        );
+    }
+
+    #[test]
+    fn plans_are_merged_when_repeated() {
+        let plans = [
+            Plan::Payback,
+            Plan::Payback,
+            Plan::Hire(Producer::SeniorDevs),
+            Plan::Payback,
+        ];
+        assert_eq!(
+            format_plans(&plans),
+            "payback x2, hire Senior devs x1, payback x1"
+        );
+    }
+}
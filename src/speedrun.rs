@@ -1,29 +1,83 @@
 #![allow(non_snake_case)]
 
-use crate::constants::Research;
-use crate::state::State;
+use crate::codebases::next_codebase;
+use crate::constants::{use_game_constants, Research};
+use crate::format_decimal::format_decimal_loc;
+use crate::format_duration::format_duration;
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
 use break_infinity::Decimal;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
-use dioxus::prelude::Signal;
 use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use web_time::Instant;
+
+/**
+ * A completed run, from the first action to the last quest
+ */
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SpeedrunRecord {
+    pub seconds: f64,
+    // simulated time of the run, differs from `seconds` when the tick rate is changed
+    pub game_time: f64,
+}
+
+/**
+ * Hundredths of seconds precision, e.g. "1:02:03.45"
+ */
+pub(crate) fn format_speedrun_time(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    let hundredth = (millis / 10u128) % 100u128;
+    let seconds = (millis / 1000u128) % 60u128;
+    let minutes = (millis / 60_000u128) % 60u128;
+    let hours = millis / 3_600_000u128;
+    format!("{}:{:02}:{:02}.{:02}", hours, minutes, seconds, hundredth)
+}
+
+/**
+ * Records the run in the history, called when the last quest is completed
+ */
+pub(crate) fn finish_speedrun(state: &mut State) {
+    let Some(start) = state.speedrun_start else {
+        return;
+    };
+    let duration = Instant::now() - start;
+    let record = SpeedrunRecord {
+        seconds: duration.as_secs_f64(),
+        game_time: state.game_time,
+    };
+    state.logs.log(
+        LogCategory::System,
+        &format!("speedrun finished in {}", format_speedrun_time(duration)),
+    );
+    state.speedrun_history.push(record);
+}
 
 #[component]
-pub(crate) fn Speedrun(state: Signal<State>, max_loc: Decimal) -> Element {
+pub(crate) fn Speedrun() -> Element {
+    let state = use_game_state();
+    let max_loc = use_game_constants().quest_differentiation_loc_cost;
     let progress = (state.read().loc.max(&Decimal::ONE).log10()
         / max_loc.max(&Decimal::ONE).log10())
     .clamp(0.0, 1.0);
     let elapsed_time = if let Some(start) = state.read().speedrun_start {
-        let duration = state.read().current_time - start;
-        let millis = duration.as_millis();
-        let hundredth = (millis / 10u128) % 100u128;
-        let seconds = (millis / 1000u128) % 60u128;
-        let minutes = (millis / 60_000u128) % 60u128;
-        let hours = millis / 3_600_000u128;
-        format!("{}:{:02}:{:02}.{:02}", hours, minutes, seconds, hundredth)
+        format_speedrun_time(state.read().current_time - start)
     } else {
         "timer not started".to_string()
     };
+    let (next_milestone, next_milestone_source) = match next_codebase(state.read().loc) {
+        Some(codebase) => (
+            format!(
+                "next: {} ({})",
+                codebase.name,
+                format_decimal_loc(Decimal::new(codebase.loc), state.read().settings.notation)
+            ),
+            codebase.source,
+        ),
+        None => ("all of GitHub written".to_string(), ""),
+    };
     rsx! {
         if state.read().researched.contains(&Research::Speedrun) {
             div {
@@ -46,6 +100,10 @@ pub(crate) fn Speedrun(state: Signal<State>, max_loc: Decimal) -> Element {
                                 value: progress,
                                 max: 1.0,
                             }
+                            div {
+                                title: next_milestone_source,
+                                "{next_milestone}"
+                            }
                         }
                         td {
                             class: "table-value",
@@ -57,3 +115,49 @@ pub(crate) fn Speedrun(state: Signal<State>, max_loc: Decimal) -> Element {
         }
     }
 }
+
+#[component]
+pub(crate) fn SpeedrunHistory() -> Element {
+    let state = use_game_state();
+    let mut runs: Vec<(usize, SpeedrunRecord)> = state
+        .read()
+        .speedrun_history
+        .iter()
+        .cloned()
+        .enumerate()
+        .collect();
+    runs.sort_by(|(_, a), (_, b)| a.seconds.total_cmp(&b.seconds));
+    rsx! {
+        div {
+            class: "speedrun-history",
+            h2 {"speedrun history"}
+            if runs.is_empty() {
+                p {"No completed run yet, differentiate the simulation to finish one."}
+            } else {
+                table {
+                    tr {
+                        th {"rank"}
+                        th {"run"}
+                        th {"time"}
+                        th {"game time"}
+                    }
+                    for (rank, (index, run)) in runs.into_iter().enumerate() {
+                        tr {
+                            class: if rank == 0 { "best-run" } else { "" },
+                            td {"{rank + 1}"}
+                            td {"#{index + 1}"}
+                            td {
+                                class: "table-value",
+                                "{format_speedrun_time(Duration::from_secs_f64(run.seconds))}"
+                            }
+                            td {
+                                class: "table-value",
+                                "{format_duration(run.game_time)}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
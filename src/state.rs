@@ -1,17 +1,90 @@
+use crate::actions::Action;
+use crate::cheats::CHEATS;
+use crate::confirmation::Undo;
 use crate::constants::{GameConstants, Research};
-use crate::simple_logs::SimpleLogs;
-use crate::Theme;
+use crate::git::Commit;
+use crate::hr_quotas::{apply_hr_quotas, HrQuotas};
+use crate::layoffs::LayoffPolicy;
+use crate::modifiers::{Modifiers, Stat};
+use crate::random_events::{RandomEvent, RandomEvents};
+use crate::rates::Rates;
+use crate::settings::Settings;
+use crate::simple_logs::{LogCategory, SimpleLogs};
+use crate::simple_rng::SimpleRng;
+use crate::speedrun::SpeedrunRecord;
+use crate::themes::Theme;
 use break_infinity::Decimal;
+use dioxus::prelude::{use_context, Signal};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use web_time::Instant;
+use web_time::{Instant, SystemTime, UNIX_EPOCH};
 
+/**
+ * Instants cannot be serialized, the speedrun start is saved as the time elapsed since then
+ */
+mod speedrun_elapsed {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::Duration;
+    use web_time::Instant;
+
+    pub(super) fn serialize<S: Serializer>(
+        start: &Option<Instant>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        start
+            .map(|start| start.elapsed().as_secs_f64())
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Instant>, D::Error> {
+        let elapsed = Option::<f64>::deserialize(deserializer)?;
+        Ok(elapsed.and_then(|elapsed| {
+            Instant::now().checked_sub(Duration::from_secs_f64(elapsed.max(0.0)))
+        }))
+    }
+}
+
+// missing fields of older saves are filled with the ones of a new game
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub(crate) struct State {
     pub logs: SimpleLogs,
     pub researched: HashSet<Research>,
     pub theme: Theme,
+    pub settings: Settings,
+    #[serde(with = "speedrun_elapsed")]
     pub speedrun_start: Option<Instant>,
+    pub speedrun_history: Vec<SpeedrunRecord>,
+    // whether this game was ever played with a build including cheats, see `cheats`
+    pub cheats_available: bool,
+    // wall time at which the page was loaded
+    #[serde(skip)]
+    pub session_start: Instant,
+    #[serde(skip)]
     pub current_time: Instant,
+    // simulated time in seconds, sum of all ticks
+    pub game_time: f64,
+    pub rng_seed: u64,
+    pub rng: SimpleRng,
+    pub random_events: RandomEvents,
+    pub modifiers: Modifiers,
+    // git checkpoints, oldest first
+    pub commits: Vec<Commit>,
+    pub layoff_policy: LayoffPolicy,
+    // hiring quota sliders, used once hiring quotas are researched
+    pub hr_quotas: HrQuotas,
+    // destructive action waiting for the player to confirm it
+    #[serde(skip)]
+    pub pending_confirmation: Option<Action>,
+    #[serde(skip)]
+    pub undo: Option<Undo>,
+    // ticks per second of the last update, to convert per tick amounts to per second ones
+    pub dt_seconds: Decimal,
     pub loc_dt: Decimal,
+    // projected loc/s gained every second, from HR hiring
+    pub loc_dt_growth: Decimal,
     pub bugs_dt: Decimal,
     pub features_dt: Decimal,
     pub loc_per_clicks: Decimal,
@@ -23,6 +96,8 @@ pub(crate) struct State {
     pub loc: Decimal,
     pub bugs: Decimal,
     pub features: Decimal,
+    // loc paid for purchases over the whole game, git revert never gives it back
+    pub loc_spent: Decimal,
     pub manual_interns: Decimal,
     pub manual_junior_devs: Decimal,
     pub manual_senior_devs: Decimal,
@@ -44,6 +119,8 @@ pub(crate) struct State {
     pub hrs_senior_devs_quota: Decimal,
     pub hrs_hrs_dt: Decimal,
     pub hrs_hrs_quota: Decimal,
+    pub hrs_pms_dt: Decimal,
+    pub hrs_pms_quota: Decimal,
     pub pms: Decimal,
     pub pms_bugs_conversion_dt: Decimal,
     pub interns_promotion_ratio_dt: Decimal,
@@ -55,14 +132,40 @@ pub(crate) struct State {
 
 impl State {
     pub(crate) fn new(constants: GameConstants) -> State {
-        let researched = HashSet::from([Research::Cheating]);
+        let rng_seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        let mut researched = HashSet::new();
+        if CHEATS {
+            researched.insert(Research::Cheating);
+        }
+        let mut rng = SimpleRng::new(rng_seed);
+        let random_events = RandomEvents::new(&constants, &mut rng);
+        let mut logs = SimpleLogs::new();
+        logs.log(LogCategory::System, &format!("random seed {}", rng_seed));
         State {
-            logs: SimpleLogs::new(),
+            logs,
             researched,
-            theme: Theme::LightTheme,
+            theme: Theme::Light,
+            settings: Settings::default(),
             speedrun_start: None,
+            speedrun_history: Vec::new(),
+            cheats_available: CHEATS,
+            session_start: Instant::now(),
             current_time: Instant::now(),
+            game_time: 0.0,
+            rng_seed,
+            rng,
+            random_events,
+            modifiers: Modifiers::default(),
+            commits: Vec::new(),
+            layoff_policy: LayoffPolicy::default(),
+            hr_quotas: HrQuotas::new(&constants),
+            pending_confirmation: None,
+            undo: None,
+            dt_seconds: Default::default(),
             loc_dt: Default::default(),
+            loc_dt_growth: Default::default(),
             bugs_dt: Default::default(),
             features_dt: Default::default(),
             loc_per_clicks: constants.loc_per_clicks,
@@ -74,6 +177,7 @@ impl State {
             loc: Default::default(),
             bugs: Default::default(),
             features: Default::default(),
+            loc_spent: Default::default(),
             manual_interns: Default::default(),
             manual_junior_devs: Default::default(),
             manual_senior_devs: Default::default(),
@@ -95,6 +199,8 @@ impl State {
             hrs_senior_devs_quota: constants.hrs_senior_devs_quota,
             hrs_hrs_dt: constants.hrs_hrs_dt,
             hrs_hrs_quota: constants.hrs_hrs_quota,
+            hrs_pms_dt: constants.hrs_pms_dt,
+            hrs_pms_quota: constants.hrs_pms_quota,
             pms: Default::default(),
             pms_bugs_conversion_dt: constants.pms_bugs_conversion_dt,
             interns_promotion_ratio_dt: constants.interns_promotion_ratio_dt,
@@ -105,108 +211,127 @@ impl State {
         }
     }
 
+    /**
+     * Pays `loc` for something the player keeps, affordability is checked by the caller
+     */
+    pub(crate) fn spend(&mut self, loc: Decimal) {
+        self.loc -= loc;
+        self.loc_spent += loc;
+    }
+
+    /**
+     * Base value of a stat, before modifiers
+     */
+    pub(crate) fn base(&self, stat: Stat) -> Decimal {
+        match stat {
+            Stat::InternsLocDt => self.interns_loc_dt,
+            Stat::JuniorDevsLocDt => self.junior_devs_loc_dt,
+            Stat::SeniorDevsLocDt => self.senior_devs_loc_dt,
+            Stat::InternsBugsRatio => self.interns_bugs_ratio,
+            Stat::JuniorDevsBugsRatio => self.junior_devs_bugs_ratio,
+            Stat::SeniorDevsBugsRatio => self.senior_devs_bugs_ratio,
+            Stat::HrsInternsDt => self.hrs_interns_dt,
+            Stat::HrsJuniorDevsDt => self.hrs_junior_devs_dt,
+            Stat::HrsSeniorDevsDt => self.hrs_senior_devs_dt,
+            Stat::HrsHrsDt => self.hrs_hrs_dt,
+            Stat::HrsPmsDt => self.hrs_pms_dt,
+            Stat::PmsBugsConversionDt => self.pms_bugs_conversion_dt,
+            Stat::InternsPromotionRatioDt => self.interns_promotion_ratio_dt,
+            Stat::JuniorDevsPromotionRatioDt => self.junior_devs_promotion_ratio_dt,
+            Stat::SeniorDevsRetirementRatioDt => self.senior_devs_retirement_ratio_dt,
+            Stat::SeniorDevsManagementRatioDt => self.senior_devs_management_ratio_dt,
+        }
+    }
+
+    /**
+     * Value of a stat once all active modifiers are applied
+     */
+    pub(crate) fn effective(&self, stat: Stat) -> Decimal {
+        self.modifiers.apply(stat, self.base(stat))
+    }
+
     pub(crate) fn update(&mut self, dt_seconds: Decimal) {
-        // loc produced by devs
-        let auto_loc = ((self.interns + self.manual_interns) * self.interns_loc_dt
-            + (self.junior_devs + self.manual_junior_devs) * self.junior_devs_loc_dt
-            + (self.senior_devs + self.manual_senior_devs) * self.senior_devs_loc_dt)
-            * self.dt;
-        // bugs produced by devs
-        let auto_bugs =
-            ((self.interns + self.manual_interns) * self.interns_loc_dt * self.interns_bugs_ratio
-                + (self.junior_devs + self.manual_junior_devs)
-                    * self.junior_devs_loc_dt
-                    * self.junior_devs_bugs_ratio
-                + (self.senior_devs + self.manual_senior_devs)
-                    * self.senior_devs_loc_dt
-                    * self.senior_devs_bugs_ratio)
-                * self.dt;
+        // drop timed modifiers (buffs from events...) before evaluating rates
+        self.modifiers.remove_expired(self.game_time);
+        self.dt_seconds = dt_seconds;
+        apply_hr_quotas(self);
+        let rates = Rates::new(self);
 
         // update loc, accounting all sources
-        self.loc += auto_loc;
+        self.loc += rates.loc;
         // update live code metrics
-        self.loc_dt = auto_loc * dt_seconds;
+        self.loc_dt = rates.loc * dt_seconds;
+        self.loc_dt_growth = (rates.hired_interns * rates.interns.loc_per_dev
+            + rates.hired_junior_devs * rates.junior_devs.loc_per_dev
+            + rates.hired_senior_devs * rates.senior_devs.loc_per_dev)
+            * dt_seconds
+            * dt_seconds;
 
-        let auto_bugs_converted_capacity =
-            (self.pms + self.manual_pms) * self.pms_bugs_conversion_dt * self.dt;
-        // make sure we do not convert more bugs than available
-        let bugs_converted = self.bugs.min(&auto_bugs_converted_capacity);
-        let bugs_delta = auto_bugs - bugs_converted;
+        let bugs_delta = rates.bugs - rates.bugs_converted;
         self.bugs += bugs_delta;
         self.bugs_dt = bugs_delta * dt_seconds;
 
-        self.features += bugs_converted;
-        self.features_dt = bugs_converted * dt_seconds;
-
-        let auto_interns =
-            (self.hrs + self.manual_hrs) * self.hrs_interns_dt * self.hrs_interns_quota * self.dt;
-        let auto_junior_devs = (self.hrs + self.manual_hrs)
-            * self.hrs_junior_devs_dt
-            * self.hrs_junior_devs_quota
-            * self.dt;
-        let auto_senior_devs = (self.hrs + self.manual_hrs)
-            * self.hrs_senior_devs_dt
-            * self.hrs_senior_devs_quota
-            * self.dt;
-        let auto_hrs = if self.researched.contains(&Research::RecursiveHR) {
-            (self.hrs + self.manual_hrs) * self.hrs_hrs_dt * self.hrs_hrs_quota * self.dt
-        } else {
-            Decimal::ZERO
-        };
-
-        // update interns, junior devs, senior devs count, accounting for all sources
-        self.interns += auto_interns;
-        self.junior_devs += auto_junior_devs;
-        self.senior_devs += auto_senior_devs;
-        self.hrs += auto_hrs;
-
-        let seniors_becoming_pms = (self.senior_devs + self.manual_senior_devs)
-            * self.senior_devs_management_ratio_dt
-            * self.dt;
-        self.pms += seniors_becoming_pms;
+        self.features += rates.bugs_converted;
+        self.features_dt = rates.bugs_converted * dt_seconds;
 
         // handle promotions & retirement...
-        let retired_seniors = (self.senior_devs + self.manual_senior_devs)
-            * self.senior_devs_retirement_ratio_dt
-            * self.dt;
-        self.retired_devs += retired_seniors;
-        let remaining_seniors =
-            self.senior_devs * (Decimal::ONE - self.senior_devs_retirement_ratio_dt * self.dt);
-        self.senior_devs = remaining_seniors;
-        let remaining_manual_seniors = self.manual_senior_devs
-            * (Decimal::ONE - self.senior_devs_retirement_ratio_dt * self.dt);
-        self.manual_senior_devs = remaining_manual_seniors;
-
-        if self.researched.contains(&Research::JuniorDevsPromotion) {
-            let juniors_promoted_to_seniors = (self.junior_devs + self.manual_junior_devs)
-                * self.junior_devs_promotion_ratio_dt
-                * self.dt;
-            self.senior_devs += juniors_promoted_to_seniors;
-            let remaining_juniors =
-                self.junior_devs * (Decimal::ONE - self.junior_devs_promotion_ratio_dt * self.dt);
-            self.junior_devs = remaining_juniors;
-            let remaining_manual_juniors = self.manual_junior_devs
-                * (Decimal::ONE - self.junior_devs_promotion_ratio_dt * self.dt);
-            self.manual_junior_devs = remaining_manual_juniors;
-        }
+        self.interns *= Decimal::ONE - rates.interns_promotion_ratio;
+        self.manual_interns *= Decimal::ONE - rates.interns_promotion_ratio;
+        self.junior_devs *= Decimal::ONE - rates.junior_devs_promotion_ratio;
+        self.manual_junior_devs *= Decimal::ONE - rates.junior_devs_promotion_ratio;
+        // senior devs leave by retiring or by becoming PMs
+        let senior_devs_leaving_ratio =
+            rates.senior_devs_retirement_ratio + rates.senior_devs_management_ratio;
+        self.senior_devs *= Decimal::ONE - senior_devs_leaving_ratio;
+        self.manual_senior_devs *= Decimal::ONE - senior_devs_leaving_ratio;
+        self.junior_devs += rates.promoted_interns;
+        self.senior_devs += rates.promoted_junior_devs;
+        // adding a negligible amount renormalizes the mantissa, which can round it down by an ulp
+        self.retired_devs = (self.retired_devs + rates.retired_senior_devs).max(&self.retired_devs);
+        self.pms += rates.senior_devs_becoming_pms;
 
-        if self.researched.contains(&Research::InternsPromotion) {
-            let interns_promoted_juniors =
-                (self.interns + self.manual_interns) * self.interns_promotion_ratio_dt * self.dt;
-            self.junior_devs += interns_promoted_juniors;
-            let remaining_interns =
-                self.interns * (Decimal::ONE - self.interns_promotion_ratio_dt * self.dt);
-            self.interns = remaining_interns;
-            let remaining_manual_interns =
-                self.manual_interns * (Decimal::ONE - self.interns_promotion_ratio_dt * self.dt);
-            self.manual_interns = remaining_manual_interns;
-        }
+        // update interns, junior devs, senior devs count, accounting for all sources
+        self.interns += rates.hired_interns;
+        self.junior_devs += rates.hired_junior_devs;
+        self.senior_devs += rates.hired_senior_devs;
+        self.hrs += rates.hired_hrs;
+        self.pms += rates.hired_pms;
 
         // update current time
         self.current_time = Instant::now();
+        self.game_time += 1.0 / dt_seconds.to_number();
+        self.logs.set_game_time(self.game_time);
+        if self
+            .undo
+            .as_ref()
+            .is_some_and(|undo| self.game_time >= undo.expires_at)
+        {
+            self.undo = None;
+        }
+
+        self.update_random_events();
+    }
+
+    fn update_random_events(&mut self) {
+        if !self.researched.contains(&Research::Internship)
+            || self.random_events.pending.is_some()
+            || self.game_time < self.random_events.next_event_time
+        {
+            return;
+        }
+        let event = RandomEvent::ALL[self.rng.index(RandomEvent::ALL.len())];
+        self.random_events.pending = Some(event);
+        self.logs.log(LogCategory::Event, event.title());
     }
 }
 
+/**
+ * Game state shared by every view, provided by the `Game` layout
+ */
+pub(crate) fn use_game_state() -> Signal<State> {
+    use_context()
+}
+
 impl Default for State {
     fn default() -> Self {
         let constants = GameConstants::default();
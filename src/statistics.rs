@@ -0,0 +1,92 @@
+#![allow(non_snake_case)]
+use crate::constants::use_game_constants;
+use crate::format_decimal::{
+    format_decimal_bugs, format_decimal_devs, format_decimal_features, format_decimal_hrs,
+    format_decimal_loc, format_decimal_pms,
+};
+use crate::format_duration::format_duration;
+use crate::quest_data::quest_definitions;
+use crate::research_data::research_definitions;
+use crate::state::use_game_state;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+
+#[component]
+pub(crate) fn Statistics() -> Element {
+    let state = use_game_state();
+    let constants = use_game_constants();
+    let state = state.read();
+    let notation = state.settings.notation;
+    let session_seconds = (state.current_time - state.session_start).as_secs_f64();
+    let devs = state.interns
+        + state.manual_interns
+        + state.junior_devs
+        + state.manual_junior_devs
+        + state.senior_devs
+        + state.manual_senior_devs;
+    let researches = research_definitions(&constants);
+    let researched = researches
+        .iter()
+        .filter(|definition| state.researched.contains(&definition.research))
+        .count();
+    let quests = quest_definitions(&constants);
+    let completed = quests
+        .iter()
+        .filter(|definition| state.researched.contains(&definition.research))
+        .count();
+    let rows = [
+        ("game time", format_duration(state.game_time)),
+        ("this session", format_duration(session_seconds)),
+        ("loc", format_decimal_loc(state.loc, notation)),
+        ("bugs", format_decimal_bugs(state.bugs, notation)),
+        (
+            "features",
+            format_decimal_features(state.features, notation),
+        ),
+        ("devs", format_decimal_devs(devs, notation)),
+        (
+            "retired devs",
+            format_decimal_devs(state.retired_devs, notation),
+        ),
+        (
+            "HRs",
+            format_decimal_hrs(state.hrs + state.manual_hrs, notation),
+        ),
+        (
+            "PMs",
+            format_decimal_pms(state.pms + state.manual_pms, notation),
+        ),
+        ("researches", format!("{}/{}", researched, researches.len())),
+        ("quests", format!("{}/{}", completed, quests.len())),
+        (
+            "active modifiers",
+            state.modifiers.iter().count().to_string(),
+        ),
+        (
+            "completed speedruns",
+            state.speedrun_history.len().to_string(),
+        ),
+        ("random seed", state.rng_seed.to_string()),
+    ];
+    rsx! {
+        div {
+            class: "statistics",
+            h2 {"statistics"}
+            table {
+                for (name, value) in rows {
+                    tr {
+                        td {
+                            class: "table-name",
+                            "{name}"
+                        }
+                        td {
+                            class: "table-value",
+                            "{value}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
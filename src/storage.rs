@@ -0,0 +1,34 @@
+use dioxus::prelude::eval;
+
+// avoid clashing with other apps served from the same origin
+const KEY_PREFIX: &str = "reasonably.";
+
+/**
+ * Persists `value` in the browser local storage
+ */
+pub(crate) fn store(key: &str, value: &str) {
+    if let Err(error) = eval(
+        r#"
+        const [key, value] = await dioxus.recv();
+        localStorage.setItem(key, value);
+        "#,
+    )
+    .send(serde_json::json!([format!("{}{}", KEY_PREFIX, key), value]))
+    {
+        dioxus_logger::tracing::error!("failed to store {}: {:?}", key, error);
+    }
+}
+
+/**
+ * Value previously persisted with `store`, None if there is none
+ */
+pub(crate) async fn load(key: &str) -> Option<String> {
+    let mut load = eval(
+        r#"
+        const key = await dioxus.recv();
+        dioxus.send(localStorage.getItem(key));
+        "#,
+    );
+    load.send(format!("{}{}", KEY_PREFIX, key).into()).ok()?;
+    load.recv().await.ok()?.as_str().map(str::to_string)
+}
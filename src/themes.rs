@@ -0,0 +1,155 @@
+#![allow(non_snake_case)]
+use crate::constants::Research;
+use crate::simple_logs::LogCategory;
+use crate::state::{use_game_state, State};
+use crate::storage;
+use dioxus::core_macro::{component, rsx};
+use dioxus::dioxus_core::Element;
+use dioxus::prelude::*;
+use dioxus::prelude::{Signal, Writable};
+use serde::{Deserialize, Serialize};
+
+const THEME_KEY: &str = "theme";
+
+/**
+ * Each theme matches a `[data-theme=...]` palette in `main.css`
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[repr(u8)]
+pub(crate) enum Theme {
+    Light,
+    Dark,
+    HighContrast,
+    ColorBlind,
+    ColorBlindDark,
+}
+
+impl Theme {
+    pub(crate) const ALL: [Theme; 5] = [
+        Theme::Light,
+        Theme::Dark,
+        Theme::HighContrast,
+        Theme::ColorBlind,
+        Theme::ColorBlindDark,
+    ];
+
+    pub(crate) fn data_theme(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high-contrast",
+            Theme::ColorBlind => "color-blind",
+            Theme::ColorBlindDark => "color-blind-dark",
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::HighContrast => "high contrast",
+            Theme::ColorBlind => "color-blind safe",
+            Theme::ColorBlindDark => "color-blind safe dark",
+        }
+    }
+
+    fn from_data_theme(data_theme: &str) -> Option<Theme> {
+        Theme::ALL
+            .into_iter()
+            .find(|theme| theme.data_theme() == data_theme)
+    }
+
+    /**
+     * Light or dark counterpart, used by the toggle theme action
+     */
+    pub(crate) fn toggled(&self) -> Theme {
+        match self {
+            Theme::Light | Theme::HighContrast => Theme::Dark,
+            Theme::Dark => Theme::Light,
+            Theme::ColorBlind => Theme::ColorBlindDark,
+            Theme::ColorBlindDark => Theme::ColorBlind,
+        }
+    }
+}
+
+fn apply_theme(theme: Theme) {
+    if let Err(error) = eval(
+        r#"
+        const theme = await dioxus.recv();
+        document.documentElement.setAttribute('data-theme', theme);
+        "#,
+    )
+    .send(theme.data_theme().into())
+    {
+        dioxus_logger::tracing::error!("failed to apply theme: {:?}", error);
+    }
+}
+
+/**
+ * Applies and persists a theme picked by the player
+ */
+pub(crate) fn select_theme(mut state: Signal<State>, theme: Theme) {
+    state.write().theme = theme;
+    apply_theme(theme);
+    storage::store(THEME_KEY, theme.data_theme());
+}
+
+/**
+ * Restores the persisted theme, falls back to the browser `prefers-color-scheme` on first load
+ */
+pub(crate) async fn restore_theme(mut state: Signal<State>) {
+    let stored = storage::load(THEME_KEY)
+        .await
+        .and_then(|data_theme| Theme::from_data_theme(&data_theme));
+    let theme = match stored {
+        Some(theme) => theme,
+        None => {
+            let mut prefers_dark = eval(
+                r#"
+                dioxus.send(window.matchMedia('(prefers-color-scheme: dark)').matches);
+                "#,
+            );
+            match prefers_dark.recv().await {
+                Ok(serde_json::Value::Bool(true)) => Theme::Dark,
+                _ => Theme::Light,
+            }
+        }
+    };
+    state.write().theme = theme;
+    apply_theme(theme);
+}
+
+/**
+ * Every theme to pick from, once the toggle theme research unlocks theming
+ */
+#[component]
+pub(crate) fn ThemePicker() -> Element {
+    let mut state = use_game_state();
+    if !state.read().researched.contains(&Research::ToggleTheme) {
+        return None;
+    }
+    let current = state.read().theme;
+    rsx! {
+        label {
+            "theme "
+            select {
+                onchange: move |event: Event<FormData>| {
+                    if let Some(theme) = Theme::from_data_theme(&event.value()) {
+                        select_theme(state, theme);
+                        state.write().logs.log(
+                            LogCategory::Action,
+                            &format!("theme...now {}", theme.name())
+                        );
+                    }
+                },
+                for theme in Theme::ALL {
+                    option {
+                        value: theme.data_theme(),
+                        selected: theme == current,
+                        "{theme.name()}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,46 +3,27 @@ use crate::constants::Research;
 use dioxus::core_macro::{component, rsx};
 use dioxus::dioxus_core::Element;
 use dioxus::prelude::*;
-use dioxus::prelude::{Signal, Writable};
+use dioxus::prelude::Writable;
 
-use crate::state::State;
-use crate::Theme;
+use crate::simple_logs::LogCategory;
+use crate::state::use_game_state;
+use crate::themes::select_theme;
 
 #[component]
-pub(crate) fn ToggleThemeAction(mut state: Signal<State>) -> Element {
-    let current_theme: Theme = state.read().theme.clone();
+pub(crate) fn ToggleThemeAction() -> Element {
+    let mut state = use_game_state();
+    let toggled_theme = state.read().theme.toggled();
     rsx! {
         if state.read().researched.contains(&Research::ToggleTheme) {
             button {
                 class: "repeatable-action-button",
                 onclick: move |_| {
-                match current_theme {
-                    Theme::LightTheme => {
-                        state.write().theme = Theme::DarkTheme;
-                        state.write().logs.log(
-                            "toggling theme...now dark"
-                        );
-                        spawn(async move {
-                            eval(r#"
-                            document.documentElement.setAttribute('data-theme', "dark")
-                            "#,
-                            ).await.expect("failed to run JS");
-                        });
-                    },
-                    Theme::DarkTheme => {
-                        state.write().theme = Theme::LightTheme;
-                        state.write().logs.log(
-                            "toggling theme...now light"
-                        );
-                        spawn(async move {
-                            eval(r#"
-                            document.documentElement.setAttribute('data-theme', "light")
-                            "#,
-                            ).await.expect("failed to run JS");
-                        });
-                    },
-                };
-            }
+                    select_theme(state, toggled_theme);
+                    state.write().logs.log(
+                        LogCategory::Action,
+                        &format!("toggling theme...now {}", toggled_theme.name())
+                    );
+                }
             , "Toggle Theme" }
         }
     }